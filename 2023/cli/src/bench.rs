@@ -0,0 +1,382 @@
+use std::fs;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use anyhow::{bail, Context, Result};
+use clap::{Parser, ValueEnum};
+use common::format_duration;
+use common::puzzle::input_file;
+use log::{info, warn};
+use serde::{Deserialize, Serialize};
+
+use crate::registry::{self, YEAR};
+
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq)]
+pub enum BaselineMode {
+    /// Store the current timings as the new baseline
+    Save,
+    /// Compare the current timings against the stored baseline
+    Compare,
+}
+
+/// Benchmark all puzzles on their inputs
+#[derive(Parser, Debug)]
+pub struct Command {
+    /// Only benchmark a single day
+    #[arg(short, long)]
+    day: Option<u8>,
+
+    /// Runs per puzzle, the median runtime is reported
+    #[arg(short, long, default_value_t = 10)]
+    runs: usize,
+
+    /// Save a new baseline or compare against the stored one
+    #[arg(long, value_enum)]
+    baseline: Option<BaselineMode>,
+
+    /// Location of the baseline file
+    #[arg(long, default_value = "bench/baseline.json")]
+    baseline_file: PathBuf,
+
+    /// Allowed slowdown per part before compare fails, e.g. `10%`
+    #[arg(long, default_value = "10%", value_parser = parse_percentage)]
+    max_regression: f64,
+
+    /// Parts faster than this (in µs) in both runs are never reported as regression
+    #[arg(long, default_value_t = 100)]
+    noise_floor_us: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Timing {
+    pub day: u8,
+    pub part: u8,
+    pub median_us: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Baseline {
+    pub year: u16,
+    pub timings: Vec<Timing>,
+}
+
+#[derive(Debug, PartialEq)]
+enum Status {
+    Ok,
+    Regression,
+    New,
+    Missing,
+}
+
+#[derive(Debug, PartialEq)]
+struct Row {
+    day: u8,
+    part: u8,
+    baseline_us: Option<u64>,
+    current_us: Option<u64>,
+    change: Option<f64>,
+    status: Status,
+}
+
+fn parse_percentage(value: &str) -> Result<f64> {
+    let number = value.trim().trim_end_matches('%');
+
+    let percentage: f64 = number
+        .parse()
+        .with_context(|| format!("Invalid percentage {value}"))?;
+
+    if percentage < 0.0 {
+        bail!("Percentage must not be negative: {value}");
+    }
+
+    Ok(percentage)
+}
+
+fn median(mut durations: Vec<Duration>) -> Duration {
+    durations.sort();
+
+    durations
+        .get(durations.len() / 2)
+        .copied()
+        .unwrap_or_default()
+}
+
+impl Command {
+    fn measure(&self) -> Result<Vec<Timing>> {
+        let mut timings = Vec::new();
+
+        let puzzles = registry::puzzles()
+            .into_iter()
+            .filter(|entry| self.day.is_none_or(|day| entry.day == day));
+
+        for entry in puzzles {
+            let input_file = input_file(entry.day);
+            let Ok(input) = fs::read_to_string(&input_file) else {
+                warn!("Skipping day {}, no input in {input_file}", entry.day);
+                continue;
+            };
+
+            info!("Benchmarking day {} ({} runs)", entry.day, self.runs);
+
+            let mut durations: Vec<Vec<Duration>> = Vec::new();
+            for _ in 0..self.runs.max(1) {
+                let solution = entry
+                    .solve(&input)
+                    .with_context(|| format!("Failed to solve day {}", entry.day))?;
+
+                for (index, part) in solution.parts.iter().enumerate() {
+                    if durations.len() <= index {
+                        durations.push(Vec::new());
+                    }
+                    durations[index].push(part.duration);
+                }
+            }
+
+            for (index, part_durations) in durations.into_iter().enumerate() {
+                timings.push(Timing {
+                    day: entry.day,
+                    part: index as u8 + 1,
+                    median_us: median(part_durations).as_micros() as u64,
+                });
+            }
+        }
+
+        Ok(timings)
+    }
+}
+
+fn compare(
+    baseline: &[Timing],
+    current: &[Timing],
+    max_regression: f64,
+    noise_floor_us: u64,
+) -> Vec<Row> {
+    let mut keys: Vec<_> = baseline
+        .iter()
+        .chain(current.iter())
+        .map(|timing| (timing.day, timing.part))
+        .collect();
+    keys.sort();
+    keys.dedup();
+
+    let find = |timings: &[Timing], day: u8, part: u8| {
+        timings
+            .iter()
+            .find(|timing| timing.day == day && timing.part == part)
+            .map(|timing| timing.median_us)
+    };
+
+    keys.into_iter()
+        .map(|(day, part)| {
+            let baseline_us = find(baseline, day, part);
+            let current_us = find(current, day, part);
+
+            let (change, status) = match (baseline_us, current_us) {
+                (Some(before), Some(after)) => {
+                    let change = if before == 0 {
+                        0.0
+                    } else {
+                        (after as f64 - before as f64) / before as f64 * 100.0
+                    };
+                    let noise = before < noise_floor_us && after < noise_floor_us;
+
+                    if change > max_regression && !noise {
+                        (Some(change), Status::Regression)
+                    } else {
+                        (Some(change), Status::Ok)
+                    }
+                }
+                (None, Some(_)) => (None, Status::New),
+                _ => (None, Status::Missing),
+            };
+
+            Row {
+                day,
+                part,
+                baseline_us,
+                current_us,
+                change,
+                status,
+            }
+        })
+        .collect()
+}
+
+fn print_timings(timings: &[Timing]) {
+    println!("{:>3} {:>4} {:>10}", "day", "part", "median");
+    for timing in timings {
+        println!(
+            "{:>3} {:>4} {:>10}",
+            timing.day,
+            timing.part,
+            format_duration(Duration::from_micros(timing.median_us))
+        );
+    }
+}
+
+fn print_diff(rows: &[Row]) {
+    let format_us = |us: Option<u64>| {
+        us.map(|us| format_duration(Duration::from_micros(us)))
+            .unwrap_or_else(|| "-".to_string())
+    };
+
+    println!(
+        "{:>3} {:>4} {:>10} {:>10} {:>9}  status",
+        "day", "part", "baseline", "current", "change"
+    );
+    for row in rows {
+        let change = row
+            .change
+            .map(|change| format!("{change:+.1}%"))
+            .unwrap_or_else(|| "-".to_string());
+        let status = match row.status {
+            Status::Ok => "ok",
+            Status::Regression => "REGRESSION",
+            Status::New => "new",
+            Status::Missing => "missing",
+        };
+
+        println!(
+            "{:>3} {:>4} {:>10} {:>10} {:>9}  {status}",
+            row.day,
+            row.part,
+            format_us(row.baseline_us),
+            format_us(row.current_us),
+            change
+        );
+    }
+}
+
+impl common::CommandRunner for Command {
+    fn run(&self) -> Result<()> {
+        let timings = self.measure()?;
+
+        match self.baseline {
+            None => print_timings(&timings),
+            Some(BaselineMode::Save) => {
+                print_timings(&timings);
+
+                if let Some(parent) = self.baseline_file.parent() {
+                    fs::create_dir_all(parent)
+                        .with_context(|| format!("Failed to create {}", parent.display()))?;
+                }
+
+                let baseline = Baseline {
+                    year: YEAR,
+                    timings,
+                };
+                let json = serde_json::to_string_pretty(&baseline)
+                    .context("Failed to serialize baseline")?;
+                fs::write(&self.baseline_file, json + "\n")
+                    .with_context(|| format!("Failed to write {}", self.baseline_file.display()))?;
+
+                info!("Saved baseline to {}", self.baseline_file.display());
+            }
+            Some(BaselineMode::Compare) => {
+                let content = fs::read_to_string(&self.baseline_file).with_context(|| {
+                    format!("Failed to read baseline {}", self.baseline_file.display())
+                })?;
+                let baseline: Baseline =
+                    serde_json::from_str(&content).context("Failed to parse baseline")?;
+
+                if baseline.year != YEAR {
+                    bail!("Baseline is for {}, not {YEAR}", baseline.year);
+                }
+
+                let baseline: Vec<_> = baseline
+                    .timings
+                    .into_iter()
+                    .filter(|timing| self.day.is_none_or(|day| timing.day == day))
+                    .collect();
+
+                let rows = compare(
+                    &baseline,
+                    &timings,
+                    self.max_regression,
+                    self.noise_floor_us,
+                );
+                print_diff(&rows);
+
+                let regressions = rows
+                    .iter()
+                    .filter(|row| row.status == Status::Regression)
+                    .count();
+                if regressions > 0 {
+                    bail!(
+                        "{regressions} part(s) regressed by more than {}%",
+                        self.max_regression
+                    );
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn timing(day: u8, part: u8, median_us: u64) -> Timing {
+        Timing {
+            day,
+            part,
+            median_us,
+        }
+    }
+
+    #[test]
+    fn test_parse_percentage() {
+        assert_eq!(parse_percentage("10%").unwrap(), 10.0);
+        assert_eq!(parse_percentage("2.5").unwrap(), 2.5);
+        assert!(parse_percentage("-1%").is_err());
+        assert!(parse_percentage("ten").is_err());
+    }
+
+    #[test]
+    fn test_median() {
+        let durations = vec![
+            Duration::from_micros(30),
+            Duration::from_micros(10),
+            Duration::from_micros(20),
+        ];
+
+        assert_eq!(median(durations), Duration::from_micros(20));
+        assert_eq!(median(Vec::new()), Duration::ZERO);
+    }
+
+    #[test]
+    fn test_compare() {
+        let baseline = vec![
+            timing(1, 1, 1_000),
+            timing(1, 2, 1_000),
+            timing(2, 1, 10),
+            timing(3, 1, 500),
+        ];
+        let current = vec![
+            timing(1, 1, 1_050),
+            timing(1, 2, 1_200),
+            timing(2, 1, 50),
+            timing(4, 1, 100),
+        ];
+
+        let rows = compare(&baseline, &current, 10.0, 100);
+        let statuses: Vec<_> = rows
+            .iter()
+            .map(|row| (row.day, row.part, &row.status))
+            .collect();
+
+        assert_eq!(
+            statuses,
+            vec![
+                (1, 1, &Status::Ok),
+                (1, 2, &Status::Regression),
+                (2, 1, &Status::Ok),
+                (3, 1, &Status::Missing),
+                (4, 1, &Status::New),
+            ]
+        );
+        assert_eq!(rows[1].change, Some(20.0));
+    }
+}
@@ -0,0 +1,188 @@
+use std::fmt::Write;
+use std::fs;
+use std::path::PathBuf;
+
+use anyhow::{bail, Context, Result};
+use clap::Parser;
+use common::puzzle::{disagreements, input_file};
+use common::{format_duration, Check, Entry};
+use log::{info, warn};
+
+use crate::registry;
+
+/// Run every solution variant on the same input and fail if they disagree
+#[derive(Parser, Debug)]
+pub struct Command {
+    /// Only check a single day
+    #[arg(short, long)]
+    day: Option<u8>,
+
+    /// Read the input from this file instead of the puzzle input
+    #[arg(short, long, conflicts_with = "size")]
+    input: Option<PathBuf>,
+
+    /// Check on a generated input of this size instead of the puzzle input
+    #[arg(short = 'n', long)]
+    size: Option<usize>,
+
+    /// Seed of the generated input
+    #[arg(short, long, default_value_t = 0, requires = "size")]
+    seed: u64,
+}
+
+impl common::CommandRunner for Command {
+    fn run(&self) -> Result<()> {
+        let puzzles: Vec<_> = registry::puzzles()
+            .into_iter()
+            .filter(|entry| self.day.is_none_or(|day| entry.day == day))
+            .collect();
+
+        if puzzles.is_empty() {
+            bail!("No puzzle registered for day {:?}", self.day);
+        }
+
+        let mut failed = Vec::new();
+
+        for entry in puzzles {
+            let Some(input) = self.input(&entry)? else {
+                continue;
+            };
+
+            info!("Cross checking day {}", entry.day);
+
+            let checks = entry
+                .crosscheck(&input)
+                .with_context(|| format!("Failed to cross check day {}", entry.day))?;
+
+            print!("{}", report(&entry, &checks));
+
+            failed.extend(
+                disagreements(&checks)
+                    .into_iter()
+                    .map(|part| format!("day {} part {part}", entry.day)),
+            );
+        }
+
+        if !failed.is_empty() {
+            bail!("Variants disagree on {}", failed.join(", "));
+        }
+
+        Ok(())
+    }
+}
+
+impl Command {
+    /// Input to check the puzzle on, `None` when a puzzle input is missing.
+    fn input(&self, entry: &Entry) -> Result<Option<String>> {
+        if let Some(size) = self.size {
+            return Ok(Some(entry.generate(size, self.seed)));
+        }
+
+        if let Some(input) = &self.input {
+            let input = fs::read_to_string(input)
+                .with_context(|| format!("Failed to read input file {}", input.display()))?;
+            return Ok(Some(input));
+        }
+
+        let input_file = input_file(entry.day);
+        match fs::read_to_string(&input_file) {
+            Ok(input) => Ok(Some(input)),
+            Err(_) if self.day.is_none() => {
+                warn!("Skipping day {}, no input in {input_file}", entry.day);
+                Ok(None)
+            }
+            Err(error) => {
+                Err(error).with_context(|| format!("Failed to read input file {input_file}"))
+            }
+        }
+    }
+}
+
+fn report(entry: &Entry, checks: &[Check]) -> String {
+    let disagreements = disagreements(checks);
+
+    let mut out = String::new();
+    let _ = writeln!(out, "Day {}: {}", entry.day, entry.title);
+    let _ = writeln!(
+        out,
+        "{:>4}  {:<16} {:<24} {:>10}",
+        "part", "variant", "answer", "time"
+    );
+
+    for check in checks {
+        let answer = match &check.outcome {
+            Ok(answer) => answer.clone(),
+            Err(error) => format!("error: {error}"),
+        };
+
+        let line = format!(
+            "{:>4}  {:<16} {:<24} {:>10}",
+            check.part,
+            check.variant,
+            answer,
+            format_duration(check.duration)
+        );
+        let _ = writeln!(out, "{}", line.trim_end());
+    }
+
+    for part in disagreements {
+        let _ = writeln!(out, "Part {part}: variants disagree");
+    }
+
+    let _ = writeln!(out);
+
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+
+    fn check(part: u8, variant: &'static str, outcome: Result<&str, &str>) -> Check {
+        Check {
+            part,
+            variant,
+            outcome: outcome.map(str::to_string).map_err(str::to_string),
+            duration: Duration::from_micros(5),
+        }
+    }
+
+    #[test]
+    fn test_report() {
+        let entry = registry::puzzles().remove(0);
+        let checks = vec![
+            check(1, "default", Ok("142")),
+            check(2, "default", Ok("281")),
+            check(2, "reference", Err("Panicked: overflow")),
+        ];
+
+        let report = report(&entry, &checks);
+        let lines: Vec<_> = report.lines().collect();
+
+        assert!(lines[0].starts_with(&format!("Day {}", entry.day)));
+        assert!(lines[2].contains("142"));
+        assert!(lines[4].contains("error: Panicked: overflow"));
+        assert_eq!(lines[5], "Part 2: variants disagree");
+    }
+
+    #[test]
+    fn test_crosscheck_variants() {
+        let puzzles = registry::puzzles()
+            .into_iter()
+            .filter(|entry| entry.variants().len() > 2);
+
+        for entry in puzzles {
+            let input = entry.generate(20, 1);
+
+            let checks = entry.crosscheck(&input).unwrap();
+
+            assert!(
+                disagreements(&checks).is_empty(),
+                "day {}: {checks:?}",
+                entry.day
+            );
+        }
+    }
+}
@@ -0,0 +1,216 @@
+use std::fmt::Write;
+use std::time::Duration;
+
+use anyhow::{bail, Result};
+use clap::Parser;
+use common::history::{self, Record, HISTORY_FILE};
+use common::{format_duration, DEFAULT_VARIANT};
+
+use crate::registry::YEAR;
+
+/// Show how answers and runtimes of a puzzle changed across revisions
+#[derive(Parser, Debug)]
+pub struct Command {
+    /// Day of the puzzle
+    #[arg(short, long)]
+    day: u8,
+
+    /// Only show a single part
+    #[arg(short, long)]
+    part: Option<u8>,
+
+    /// Solution variant to follow, runs of other variants are left out
+    #[arg(long, default_value = DEFAULT_VARIANT)]
+    variant: String,
+}
+
+impl common::CommandRunner for Command {
+    fn run(&self) -> Result<()> {
+        let records = history::load(HISTORY_FILE)?;
+
+        let records: Vec<_> = records
+            .into_iter()
+            .filter(|record| record.year == YEAR && record.day == self.day)
+            .filter(|record| self.part.is_none_or(|part| record.part == part))
+            .filter(|record| record.variant == self.variant)
+            .collect();
+
+        if records.is_empty() {
+            bail!(
+                "No runs recorded for day {} ({}) in {HISTORY_FILE}",
+                self.day,
+                self.variant
+            );
+        }
+
+        print!("{}", report(&records));
+
+        Ok(())
+    }
+}
+
+struct Revision<'a> {
+    revision: &'a str,
+    answers: Vec<&'a str>,
+    durations: Vec<u64>,
+}
+
+impl Revision<'_> {
+    fn best(&self) -> u64 {
+        self.durations.iter().copied().min().unwrap_or_default()
+    }
+
+    fn median(&self) -> u64 {
+        let mut durations = self.durations.clone();
+        durations.sort();
+
+        durations
+            .get(durations.len() / 2)
+            .copied()
+            .unwrap_or_default()
+    }
+}
+
+/// Groups the runs of each part by revision, in the order the revisions were first seen.
+fn group(records: &[Record], part: u8) -> Vec<Revision<'_>> {
+    let mut revisions: Vec<Revision> = Vec::new();
+
+    for record in records.iter().filter(|record| record.part == part) {
+        let revision = match revisions
+            .iter_mut()
+            .find(|revision| revision.revision == record.revision)
+        {
+            Some(revision) => revision,
+            None => {
+                revisions.push(Revision {
+                    revision: &record.revision,
+                    answers: Vec::new(),
+                    durations: Vec::new(),
+                });
+                revisions.last_mut().unwrap()
+            }
+        };
+
+        if !revision.answers.contains(&record.answer.as_str()) {
+            revision.answers.push(&record.answer);
+        }
+        revision.durations.push(record.duration_us);
+    }
+
+    revisions
+}
+
+fn report(records: &[Record]) -> String {
+    let mut parts: Vec<_> = records
+        .iter()
+        .map(|record| (record.day, record.part))
+        .collect();
+    parts.sort();
+    parts.dedup();
+
+    let mut out = String::new();
+
+    for (day, part) in parts {
+        let _ = writeln!(out, "Day {day}, part {part}");
+        let _ = writeln!(
+            out,
+            "{:<16} {:>5}  {:<20} {:>10} {:>10} {:>8}",
+            "revision", "runs", "answer", "best", "median", "change"
+        );
+
+        let mut previous: Option<&Revision> = None;
+        let revisions = group(records, part);
+
+        for revision in &revisions {
+            let mut answer = revision.answers.join(", ");
+            if revision.answers.len() > 1 {
+                answer.push_str(" (unstable)");
+            } else if previous.is_some_and(|previous| previous.answers != revision.answers) {
+                answer.push_str(" (changed)");
+            }
+
+            let change = match previous {
+                Some(previous) if previous.median() > 0 => {
+                    let change = (revision.median() as f64 - previous.median() as f64)
+                        / previous.median() as f64
+                        * 100.0;
+                    format!("{change:+.1}%")
+                }
+                _ => String::new(),
+            };
+
+            let line = format!(
+                "{:<16} {:>5}  {:<20} {:>10} {:>10} {:>8}",
+                revision.revision,
+                revision.durations.len(),
+                answer,
+                format_duration(Duration::from_micros(revision.best())),
+                format_duration(Duration::from_micros(revision.median())),
+                change
+            );
+            let _ = writeln!(out, "{}", line.trim_end());
+
+            previous = Some(revision);
+        }
+
+        let _ = writeln!(out);
+    }
+
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(revision: &str, part: u8, answer: &str, duration_us: u64) -> Record {
+        Record {
+            year: YEAR,
+            day: 4,
+            part,
+            variant: DEFAULT_VARIANT.to_string(),
+            answer: answer.to_string(),
+            duration_us,
+            revision: revision.to_string(),
+            timestamp: 0,
+        }
+    }
+
+    #[test]
+    fn test_group() {
+        let records = vec![
+            record("aaa", 2, "30", 400),
+            record("aaa", 2, "30", 200),
+            record("aaa", 2, "30", 300),
+            record("bbb", 2, "30", 100),
+            record("aaa", 1, "13", 1),
+        ];
+
+        let revisions = group(&records, 2);
+
+        assert_eq!(revisions.len(), 2);
+        assert_eq!(revisions[0].revision, "aaa");
+        assert_eq!(revisions[0].answers, vec!["30"]);
+        assert_eq!(revisions[0].best(), 200);
+        assert_eq!(revisions[0].median(), 300);
+        assert_eq!(revisions[1].revision, "bbb");
+        assert_eq!(revisions[1].median(), 100);
+    }
+
+    #[test]
+    fn test_report() {
+        let records = vec![
+            record("aaa", 2, "30", 400),
+            record("bbb", 2, "30", 100),
+            record("ccc", 2, "31", 100),
+        ];
+
+        let report = report(&records);
+        let lines: Vec<_> = report.lines().collect();
+
+        assert_eq!(lines[0], "Day 4, part 2");
+        assert!(lines[2].starts_with("aaa"));
+        assert!(lines[3].ends_with("-75.0%"));
+        assert!(lines[4].contains("31 (changed)"));
+    }
+}
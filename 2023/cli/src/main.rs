@@ -1,6 +1,15 @@
 extern crate ansi_term;
 extern crate clap_verbosity_flag;
 extern crate loggerv;
+
+mod bench;
+mod crosscheck;
+mod gen;
+mod history;
+mod minimize;
+mod registry;
+mod serve;
+
 use anyhow::{Context, Result};
 
 use common::CommandRunner;
@@ -13,7 +22,7 @@ fn main() -> Result<()> {
 
     setup(&args).context("Failed to setup application environment")?;
 
-    args.puzzle.run()?;
+    args.command.run()?;
 
     Ok(())
 }
@@ -40,24 +49,36 @@ fn setup(opt: &Args) -> Result<()> {
 }
 
 #[derive(Subcommand, Debug)]
-enum Puzzles {
+enum Commands {
     HelloWorld(hello_world::Command),
     Puzzle1(puzzle_1::Command),
     Puzzle2(puzzle_2::Command),
     Puzzle3(puzzle_3::Command),
     Puzzle4(puzzle_4::Command),
     Puzzle5(puzzle_5::Command),
+    Serve(serve::Command),
+    History(history::Command),
+    Bench(bench::Command),
+    Gen(gen::Command),
+    Crosscheck(crosscheck::Command),
+    Minimize(minimize::Command),
 }
 
-impl Puzzles {
+impl Commands {
     fn run(&self) -> Result<()> {
         match self {
-            Puzzles::HelloWorld(cmd) => cmd.run().context("Failed to run hello world"),
-            Puzzles::Puzzle1(cmd) => cmd.run().context("Failed to run puzzle 1"),
-            Puzzles::Puzzle2(cmd) => cmd.run().context("Failed to run puzzle 2"),
-            Puzzles::Puzzle3(cmd) => cmd.run().context("Failed to run puzzle 3"),
-            Puzzles::Puzzle4(cmd) => cmd.run().context("Failed to run puzzle 4"),
-            Puzzles::Puzzle5(cmd) => cmd.run().context("Failed to run puzzle 5"),
+            Commands::HelloWorld(cmd) => cmd.run().context("Failed to run hello world"),
+            Commands::Puzzle1(cmd) => cmd.run().context("Failed to run puzzle 1"),
+            Commands::Puzzle2(cmd) => cmd.run().context("Failed to run puzzle 2"),
+            Commands::Puzzle3(cmd) => cmd.run().context("Failed to run puzzle 3"),
+            Commands::Puzzle4(cmd) => cmd.run().context("Failed to run puzzle 4"),
+            Commands::Puzzle5(cmd) => cmd.run().context("Failed to run puzzle 5"),
+            Commands::Serve(cmd) => cmd.run().context("Failed to run solve service"),
+            Commands::History(cmd) => cmd.run().context("Failed to show run history"),
+            Commands::Bench(cmd) => cmd.run().context("Failed to run benchmarks"),
+            Commands::Gen(cmd) => cmd.run().context("Failed to generate input"),
+            Commands::Crosscheck(cmd) => cmd.run().context("Failed to cross check variants"),
+            Commands::Minimize(cmd) => cmd.run().context("Failed to minimize input"),
         }
     }
 }
@@ -73,5 +94,5 @@ struct Args {
     debug: bool,
 
     #[command(subcommand)]
-    puzzle: Puzzles,
+    command: Commands,
 }
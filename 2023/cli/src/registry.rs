@@ -0,0 +1,21 @@
+use anyhow::{Context, Result};
+use common::Entry;
+
+pub const YEAR: u16 = 2023;
+
+pub fn puzzles() -> Vec<Entry> {
+    vec![
+        Entry::of::<puzzle_1::Solver>(),
+        Entry::of::<puzzle_2::Solver>(),
+        Entry::of::<puzzle_3::Solver>(),
+        Entry::of::<puzzle_4::Solver>(),
+        Entry::of::<puzzle_5::Solver>(),
+    ]
+}
+
+pub fn find(day: u8) -> Result<Entry> {
+    puzzles()
+        .into_iter()
+        .find(|entry| entry.day == day)
+        .with_context(|| format!("No puzzle for day {day} in {YEAR}"))
+}
@@ -0,0 +1,163 @@
+use std::panic;
+
+use anyhow::{anyhow, Context, Result};
+use clap::Parser;
+use log::{info, warn};
+use serde_json::{json, Value};
+use tiny_http::{Header, Method, Response, Server};
+
+use crate::registry::{self, YEAR};
+
+/// Serve the solvers over a local HTTP API
+#[derive(Parser, Debug)]
+pub struct Command {
+    /// Port to listen on, only bound to localhost
+    #[arg(short, long, default_value_t = 8023)]
+    port: u16,
+}
+
+impl common::CommandRunner for Command {
+    fn run(&self) -> Result<()> {
+        let server = Server::http(("127.0.0.1", self.port))
+            .map_err(|error| anyhow!("Failed to bind to port {}: {error}", self.port))?;
+
+        info!("Listening on http://127.0.0.1:{}", self.port);
+
+        let content_type = Header::from_bytes("Content-Type", "application/json")
+            .map_err(|_| anyhow!("Invalid content type header"))?;
+
+        for mut request in server.incoming_requests() {
+            let mut body = String::new();
+            let (status, value) = match request.as_reader().read_to_string(&mut body) {
+                Ok(_) => handle(request.method(), request.url(), &body),
+                Err(error) => error_response(400, format!("Failed to read body: {error}")),
+            };
+
+            info!("{} {} -> {status}", request.method(), request.url());
+
+            let response = Response::from_string(value.to_string())
+                .with_status_code(status)
+                .with_header(content_type.clone());
+
+            if let Err(error) = request.respond(response) {
+                warn!("Failed to send response: {error}");
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn error_response(status: u16, message: String) -> (u16, Value) {
+    (status, json!({ "error": message }))
+}
+
+fn handle(method: &Method, url: &str, body: &str) -> (u16, Value) {
+    let path: Vec<_> = url
+        .split('?')
+        .next()
+        .unwrap_or_default()
+        .split('/')
+        .filter(|segment| !segment.is_empty())
+        .collect();
+
+    match (method, path.as_slice()) {
+        (Method::Get, ["puzzles"]) => (200, list_puzzles()),
+        (Method::Post, ["solve", year, day]) => match solve(year, day, body) {
+            Ok(value) => (200, value),
+            Err((status, error)) => error_response(status, format!("{error:#}")),
+        },
+        (_, ["puzzles"]) | (_, ["solve", _, _]) => {
+            error_response(405, format!("Method {method} not allowed"))
+        }
+        _ => error_response(404, format!("No route for {url}")),
+    }
+}
+
+fn list_puzzles() -> Value {
+    registry::puzzles()
+        .iter()
+        .map(|entry| json!({ "year": YEAR, "day": entry.day, "title": entry.title }))
+        .collect()
+}
+
+fn solve(year: &str, day: &str, input: &str) -> Result<Value, (u16, anyhow::Error)> {
+    let year: u16 = year
+        .parse()
+        .with_context(|| format!("Invalid year {year}"))
+        .map_err(|error| (400, error))?;
+    let day: u8 = day
+        .parse()
+        .with_context(|| format!("Invalid day {day}"))
+        .map_err(|error| (400, error))?;
+
+    if year != YEAR {
+        return Err((404, anyhow!("Only {YEAR} is served here, not {year}")));
+    }
+
+    let entry = registry::find(day).map_err(|error| (404, error))?;
+
+    let solution = panic::catch_unwind(|| entry.solve(input))
+        .map_err(|_| (500, anyhow!("Solver for day {day} panicked")))?
+        .map_err(|error| (422, error))?;
+
+    let parts: Vec<_> = solution
+        .parts
+        .iter()
+        .map(|part| {
+            json!({
+                "part": part.part,
+                "variant": part.variant,
+                "answer": part.answer,
+                "duration_us": part.duration.as_micros() as u64,
+            })
+        })
+        .collect();
+
+    Ok(json!({
+        "year": YEAR,
+        "day": day,
+        "title": entry.title,
+        "parse_duration_us": solution.parse_duration.as_micros() as u64,
+        "parts": parts,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_list_puzzles() {
+        let (status, value) = handle(&Method::Get, "/puzzles", "");
+
+        assert_eq!(status, 200);
+        assert_eq!(
+            value[0],
+            json!({ "year": YEAR, "day": 1, "title": "Trebuchet?!" })
+        );
+    }
+
+    #[test]
+    fn test_solve() {
+        let input = "Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53
+Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19";
+
+        let (status, value) = handle(&Method::Post, "/solve/2023/4", input);
+
+        assert_eq!(status, 200);
+        assert_eq!(value["day"], 4);
+        assert_eq!(value["parts"][0]["answer"], "10");
+        assert_eq!(value["parts"][1]["answer"], "3");
+    }
+
+    #[test]
+    fn test_solve_errors() {
+        assert_eq!(handle(&Method::Post, "/solve/2022/4", "").0, 404);
+        assert_eq!(handle(&Method::Post, "/solve/2023/24", "").0, 404);
+        assert_eq!(handle(&Method::Post, "/solve/2023/x", "").0, 400);
+        assert_eq!(handle(&Method::Post, "/solve/2023/4", "Card x").0, 422);
+        assert_eq!(handle(&Method::Get, "/solve/2023/4", "").0, 405);
+        assert_eq!(handle(&Method::Get, "/unknown", "").0, 404);
+    }
+}
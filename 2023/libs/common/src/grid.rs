@@ -0,0 +1,540 @@
+use std::collections::BTreeSet;
+use std::fmt::{self, Display};
+use std::ops::{Index, IndexMut, Range};
+
+use anyhow::{bail, Context, Result};
+
+use crate::geom::Point;
+
+const NEIGHBOURS4: [(isize, isize); 4] = [(-1, 0), (0, -1), (0, 1), (1, 0)];
+
+const NEIGHBOURS8: [(isize, isize); 8] = [
+    (-1, -1),
+    (-1, 0),
+    (-1, 1),
+    (0, -1),
+    (0, 1),
+    (1, -1),
+    (1, 0),
+    (1, 1),
+];
+
+/// A run of digits within a single row of a grid.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct NumberSpan {
+    pub value: u64,
+    pub row: usize,
+    pub cols: Range<usize>,
+}
+
+impl NumberSpan {
+    /// Positions around the span, diagonals included, as far as they are inside the grid.
+    pub fn adjacent<T>(&self, grid: &Grid<T>) -> BTreeSet<(usize, usize)> {
+        self.cols
+            .clone()
+            .flat_map(|col| grid.neighbours8(self.row, col))
+            .filter(|(row, col)| *row != self.row || !self.cols.contains(col))
+            .collect()
+    }
+}
+
+/// Rectangular grid, stored row-major in a single `Vec`.
+///
+/// Cells are addressed as `(row, col)`, starting at the top left.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl Grid<char> {
+    /// Grid of the characters of `input`, one row per line.
+    pub fn parse(input: &str) -> Result<Self> {
+        Self::parse_with(input, Ok)
+    }
+
+    /// Every number in the grid, row by row. Numbers end at the end of their row.
+    pub fn numbers(&self) -> Result<Vec<NumberSpan>> {
+        self.numbers_by(|c| c.to_digit(10).map(|digit| digit as u8))
+    }
+}
+
+/// Cells of an ASCII map, one character each. Usually derived with
+/// `#[derive(Cell)]` from a legend of characters on the variants.
+pub trait Cell: Sized {
+    fn from_char(c: char) -> Result<Self>;
+
+    fn to_char(&self) -> char;
+}
+
+pub use common_derive::Cell;
+
+impl<T: Cell> Grid<T> {
+    /// Grid of the typed cells of `input`, one row per line.
+    pub fn parse_cells(input: &str) -> Result<Self> {
+        Self::parse_with(input, T::from_char)
+    }
+}
+
+impl<T> Grid<T> {
+    pub fn new(width: usize, height: usize, cells: Vec<T>) -> Result<Self> {
+        if cells.len() != width * height {
+            bail!(
+                "Expected {} cells for a {width}x{height} grid, got {}",
+                width * height,
+                cells.len()
+            );
+        }
+
+        Ok(Self {
+            width,
+            height,
+            cells,
+        })
+    }
+
+    /// Grid of `input`, one row per line, with `cell` turning characters into cells.
+    ///
+    /// Whitespace around the lines is ignored, so indented test data parses as well.
+    pub fn parse_with(input: &str, mut cell: impl FnMut(char) -> Result<T>) -> Result<Self> {
+        let mut width = None;
+        let mut height = 0;
+        let mut cells = Vec::new();
+
+        for (row, line) in input.lines().map(str::trim).enumerate() {
+            let len = line.chars().count();
+            match width {
+                None => width = Some(len),
+                Some(width) if width != len => {
+                    bail!("Row {row} has {len} cells, expected {width}")
+                }
+                Some(_) => {}
+            }
+
+            for (col, c) in line.chars().enumerate() {
+                cells.push(cell(c).with_context(|| format!("Invalid cell {c:?} at {row}/{col}"))?);
+            }
+            height += 1;
+        }
+
+        Self::new(width.unwrap_or_default(), height, cells)
+    }
+
+    /// Every number in the grid, row by row, with `digit` telling the value of
+    /// digit cells. Numbers end at the end of their row.
+    pub fn numbers_by(&self, digit: impl Fn(&T) -> Option<u8>) -> Result<Vec<NumberSpan>> {
+        let mut numbers = Vec::new();
+
+        for (row, cells) in self.rows().enumerate() {
+            let mut col = 0;
+
+            while col < cells.len() {
+                if digit(&cells[col]).is_none() {
+                    col += 1;
+                    continue;
+                }
+
+                let start = col;
+                let mut value: u64 = 0;
+                while let Some(digit) = cells.get(col).and_then(&digit) {
+                    if digit > 9 {
+                        bail!("Invalid digit {digit} at {row}/{col}");
+                    }
+                    value = value
+                        .checked_mul(10)
+                        .and_then(|value| value.checked_add(digit.into()))
+                        .with_context(|| format!("Number at {row}/{start} is too large"))?;
+                    col += 1;
+                }
+
+                numbers.push(NumberSpan {
+                    value,
+                    row,
+                    cols: start..col,
+                });
+            }
+        }
+
+        Ok(numbers)
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, row: usize, col: usize) -> bool {
+        row < self.height && col < self.width
+    }
+
+    pub fn get(&self, row: usize, col: usize) -> Option<&T> {
+        self.contains(row, col)
+            .then(|| &self.cells[row * self.width + col])
+    }
+
+    pub fn get_mut(&mut self, row: usize, col: usize) -> Option<&mut T> {
+        self.contains(row, col)
+            .then(|| &mut self.cells[row * self.width + col])
+    }
+
+    /// Cell at `point`, with `x` as the column and `y` as the row.
+    pub fn get_point(&self, point: Point) -> Option<&T> {
+        let row = usize::try_from(point.y).ok()?;
+        let col = usize::try_from(point.x).ok()?;
+
+        self.get(row, col)
+    }
+
+    fn offsets(
+        &self,
+        row: usize,
+        col: usize,
+        offsets: &'static [(isize, isize)],
+    ) -> impl Iterator<Item = (usize, usize)> + '_ {
+        offsets.iter().filter_map(move |(d_row, d_col)| {
+            let row = row.checked_add_signed(*d_row)?;
+            let col = col.checked_add_signed(*d_col)?;
+
+            self.contains(row, col).then_some((row, col))
+        })
+    }
+
+    /// Positions above, left, right and below of a cell, as far as they are inside the grid.
+    pub fn neighbours4(&self, row: usize, col: usize) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.offsets(row, col, &NEIGHBOURS4)
+    }
+
+    /// Positions around a cell including the diagonals, as far as they are inside the grid.
+    pub fn neighbours8(&self, row: usize, col: usize) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.offsets(row, col, &NEIGHBOURS8)
+    }
+
+    /// All cells with their position, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = ((usize, usize), &T)> {
+        self.cells
+            .iter()
+            .enumerate()
+            .map(|(index, cell)| ((index / self.width, index % self.width), cell))
+    }
+
+    pub fn row(&self, row: usize) -> Option<&[T]> {
+        (row < self.height).then(|| &self.cells[row * self.width..(row + 1) * self.width])
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        (0..self.height).map(|row| &self.cells[row * self.width..(row + 1) * self.width])
+    }
+
+    pub fn column(&self, col: usize) -> impl Iterator<Item = &T> {
+        let len = if col < self.width { self.height } else { 0 };
+
+        self.cells
+            .iter()
+            .skip(col)
+            .step_by(self.width.max(1))
+            .take(len)
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(|col| self.column(col))
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+
+    fn rearranged(
+        &self,
+        width: usize,
+        height: usize,
+        source: impl Fn(usize, usize) -> (usize, usize),
+    ) -> Grid<T>
+    where
+        T: Clone,
+    {
+        let cells = (0..height)
+            .flat_map(|row| (0..width).map(move |col| (row, col)))
+            .map(|(row, col)| self[source(row, col)].clone())
+            .collect();
+
+        Grid {
+            width,
+            height,
+            cells,
+        }
+    }
+
+    /// Mirrors the grid along its main diagonal, rows become columns.
+    pub fn transpose(&self) -> Grid<T>
+    where
+        T: Clone,
+    {
+        self.rearranged(self.height, self.width, |row, col| (col, row))
+    }
+
+    /// Rotates the grid clockwise by a quarter turn.
+    pub fn rotate_right(&self) -> Grid<T>
+    where
+        T: Clone,
+    {
+        self.rearranged(self.height, self.width, |row, col| {
+            (self.height - 1 - col, row)
+        })
+    }
+
+    /// Rotates the grid counterclockwise by a quarter turn.
+    pub fn rotate_left(&self) -> Grid<T>
+    where
+        T: Clone,
+    {
+        self.rearranged(self.height, self.width, |row, col| {
+            (col, self.width - 1 - row)
+        })
+    }
+}
+
+impl<T> Index<(usize, usize)> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, (row, col): (usize, usize)) -> &T {
+        self.get(row, col)
+            .unwrap_or_else(|| panic!("{row}/{col} is outside of {}x{}", self.width, self.height))
+    }
+}
+
+impl<T> Index<Point> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, point: Point) -> &T {
+        self.get_point(point)
+            .unwrap_or_else(|| panic!("{point} is outside of {}x{}", self.width, self.height))
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Grid<T> {
+    fn index_mut(&mut self, (row, col): (usize, usize)) -> &mut T {
+        let (width, height) = (self.width, self.height);
+
+        self.get_mut(row, col)
+            .unwrap_or_else(|| panic!("{row}/{col} is outside of {width}x{height}"))
+    }
+}
+
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (index, row) in self.rows().enumerate() {
+            if index > 0 {
+                writeln!(f)?;
+            }
+            for cell in row {
+                write!(f, "{cell}")?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_grid() -> Grid<char> {
+        Grid::parse(
+            "abc
+            def",
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_parse() {
+        let grid = test_grid();
+
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid.get(1, 0), Some(&'d'));
+        assert_eq!(grid.get(2, 0), None);
+        assert_eq!(grid.get(0, 3), None);
+        assert_eq!(grid[(0, 2)], 'c');
+
+        assert!(Grid::parse("ab\nc").is_err());
+        assert_eq!(Grid::parse("").unwrap().width(), 0);
+    }
+
+    #[test]
+    fn test_numbers() {
+        let grid = Grid::parse(
+            "12..
+            .*.7
+            3.45",
+        )
+        .unwrap();
+
+        let numbers = grid.numbers().unwrap();
+        let values: Vec<_> = numbers
+            .iter()
+            .map(|number| (number.value, number.row, number.cols.clone()))
+            .collect();
+
+        assert_eq!(
+            values,
+            vec![(12, 0, 0..2), (7, 1, 3..4), (3, 2, 0..1), (45, 2, 2..4)]
+        );
+        assert!(Grid::parse("99999999999999999999")
+            .unwrap()
+            .numbers()
+            .is_err());
+        assert!(grid.numbers_by(|_| Some(250)).is_err());
+    }
+
+    #[test]
+    fn test_adjacent() {
+        let grid = Grid::parse(
+            "....
+            .12.
+            ....",
+        )
+        .unwrap();
+        let number = &grid.numbers().unwrap()[0];
+
+        let adjacent = number.adjacent(&grid);
+
+        assert_eq!(adjacent.len(), 10);
+        assert!(!adjacent.contains(&(1, 1)));
+        assert!(adjacent.contains(&(0, 0)));
+        assert!(adjacent.contains(&(2, 3)));
+
+        let grid = Grid::parse("1.\n..").unwrap();
+        let corner = &grid.numbers().unwrap()[0];
+        assert_eq!(corner.adjacent(&grid).len(), 3);
+    }
+
+    #[test]
+    fn test_get_point() {
+        let grid = test_grid();
+
+        assert_eq!(grid.get_point(Point::new(2, 1)), Some(&'f'));
+        assert_eq!(grid.get_point(Point::new(-1, 0)), None);
+        assert_eq!(grid[Point::new(0, 1)], 'd');
+    }
+
+    #[test]
+    fn test_parse_with() {
+        let grid = Grid::parse_with("12\n34", |c| c.to_digit(10).context("Not a digit")).unwrap();
+
+        assert_eq!(
+            grid.rows()
+                .map(|row| row.iter().sum::<u32>())
+                .collect::<Vec<_>>(),
+            vec![3, 7]
+        );
+        assert!(Grid::parse_with("1x", |c| c.to_digit(10).context("Not a digit")).is_err());
+    }
+
+    #[test]
+    fn test_neighbours() {
+        let grid = test_grid();
+
+        assert_eq!(
+            grid.neighbours4(0, 0).collect::<Vec<_>>(),
+            vec![(0, 1), (1, 0)]
+        );
+        assert_eq!(
+            grid.neighbours8(1, 1).collect::<Vec<_>>(),
+            vec![(0, 0), (0, 1), (0, 2), (1, 0), (1, 2)]
+        );
+    }
+
+    #[test]
+    fn test_rows_and_columns() {
+        let grid = test_grid();
+
+        assert_eq!(grid.row(1), Some(&['d', 'e', 'f'][..]));
+        assert_eq!(grid.row(2), None);
+        assert_eq!(grid.column(1).collect::<String>(), "be");
+        assert_eq!(grid.column(3).count(), 0);
+        assert_eq!(
+            grid.columns()
+                .map(|col| col.collect())
+                .collect::<Vec<String>>(),
+            vec!["ad", "be", "cf"]
+        );
+        assert_eq!(grid.iter().nth(4), Some(((1, 1), &'e')));
+    }
+
+    #[test]
+    fn test_transpose_and_rotate() {
+        let grid = test_grid();
+
+        assert_eq!(grid.transpose().to_string(), "ad\nbe\ncf");
+        assert_eq!(grid.rotate_right().to_string(), "da\neb\nfc");
+        assert_eq!(grid.rotate_left().to_string(), "cf\nbe\nad");
+        assert_eq!(grid.rotate_left().rotate_right(), grid);
+        assert_eq!(grid.transpose().transpose(), grid);
+    }
+
+    #[test]
+    fn test_display() {
+        let grid = Grid::new(2, 2, vec![1, 2, 3, 4]).unwrap();
+
+        assert_eq!(grid.to_string(), "12\n34");
+        assert!(Grid::new(2, 2, vec![1]).is_err());
+    }
+
+    #[derive(Debug, PartialEq)]
+    enum Tile {
+        Open,
+        Wall,
+        Digit(u8),
+    }
+
+    impl Cell for Tile {
+        fn from_char(c: char) -> Result<Self> {
+            Ok(match c {
+                '.' => Self::Open,
+                '#' => Self::Wall,
+                c if c.is_ascii_digit() => Self::Digit(c as u8 - b'0'),
+                c => bail!("Unknown tile {c:?}"),
+            })
+        }
+
+        fn to_char(&self) -> char {
+            match self {
+                Self::Open => '.',
+                Self::Wall => '#',
+                Self::Digit(digit) => char::from(b'0' + digit),
+            }
+        }
+    }
+
+    #[test]
+    fn test_parse_cells() {
+        let grid: Grid<Tile> = Grid::parse_cells("#.4\n12#").unwrap();
+
+        assert_eq!(grid[(0, 0)], Tile::Wall);
+        assert_eq!(grid[(1, 1)], Tile::Digit(2));
+        assert_eq!(grid.map(Tile::to_char).to_string(), "#.4\n12#");
+
+        let digits = |tile: &Tile| match tile {
+            Tile::Digit(digit) => Some(*digit),
+            _ => None,
+        };
+        let numbers = grid.numbers_by(digits).unwrap();
+        assert_eq!(numbers.len(), 2);
+        assert_eq!((numbers[1].value, numbers[1].cols.clone()), (12, 0..2));
+
+        let error = Grid::<Tile>::parse_cells("#x").unwrap_err();
+        assert_eq!(
+            format!("{error:#}"),
+            "Invalid cell 'x' at 0/1: Unknown tile 'x'"
+        );
+    }
+}
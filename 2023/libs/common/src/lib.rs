@@ -1,5 +1,22 @@
+pub mod geom;
+pub mod grid;
+pub mod history;
+pub mod interval;
+pub mod memo;
+pub mod parse;
+pub mod puzzle;
+pub mod rng;
+pub mod search;
+pub mod section;
+
 use anyhow::Result;
 
+pub use common_derive::AocParse;
+
+pub use crate::puzzle::{
+    format_duration, run, Check, Entry, PartSolution, Puzzle, Solution, Variant, DEFAULT_VARIANT,
+};
+
 pub trait CommandRunner {
     fn run(&self) -> Result<()>;
 }
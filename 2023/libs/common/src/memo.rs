@@ -0,0 +1,181 @@
+//! Caching for pure recursive functions, keyed by their arguments.
+//!
+//! The function computing a missing value gets the [`Memo`] passed back in,
+//! so its recursive calls go through the cache as well.
+
+use std::collections::HashMap;
+use std::fmt::{self, Display};
+use std::hash::Hash;
+
+/// How often a [`Memo`] could answer from its cache.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Stats {
+    pub hits: u64,
+    pub misses: u64,
+    pub entries: usize,
+}
+
+impl Stats {
+    pub fn hit_rate(&self) -> f64 {
+        match self.hits + self.misses {
+            0 => 0.0,
+            calls => self.hits as f64 / calls as f64,
+        }
+    }
+}
+
+impl Display for Stats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} hits, {} misses, {} entries ({:.1}% hit rate)",
+            self.hits,
+            self.misses,
+            self.entries,
+            self.hit_rate() * 100.0
+        )
+    }
+}
+
+/// Cache of the results of a function, with hit and miss counts.
+#[derive(Debug, Clone)]
+pub struct Memo<K, V> {
+    cache: HashMap<K, V>,
+    hits: u64,
+    misses: u64,
+}
+
+impl<K: Eq + Hash, V: Clone> Default for Memo<K, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K: Eq + Hash, V: Clone> Memo<K, V> {
+    pub fn new() -> Self {
+        Self {
+            cache: HashMap::new(),
+            hits: 0,
+            misses: 0,
+        }
+    }
+
+    /// The cached value for `key`, or the one `compute` returns for it.
+    pub fn get(&mut self, key: K, compute: impl FnOnce(&mut Self, &K) -> V) -> V {
+        if let Some(value) = self.cache.get(&key) {
+            self.hits += 1;
+            return value.clone();
+        }
+
+        self.misses += 1;
+        let value = compute(self, &key);
+        self.cache.insert(key, value.clone());
+
+        value
+    }
+
+    /// Like [`Memo::get`] for functions that can fail, errors are not cached.
+    pub fn try_get<E>(
+        &mut self,
+        key: K,
+        compute: impl FnOnce(&mut Self, &K) -> Result<V, E>,
+    ) -> Result<V, E> {
+        if let Some(value) = self.cache.get(&key) {
+            self.hits += 1;
+            return Ok(value.clone());
+        }
+
+        self.misses += 1;
+        let value = compute(self, &key)?;
+        self.cache.insert(key, value.clone());
+
+        Ok(value)
+    }
+
+    pub fn stats(&self) -> Stats {
+        Stats {
+            hits: self.hits,
+            misses: self.misses,
+            entries: self.cache.len(),
+        }
+    }
+
+    /// Forgets all cached values and resets the counts.
+    pub fn clear(&mut self) {
+        self.cache.clear();
+        self.hits = 0;
+        self.misses = 0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::{bail, Result};
+
+    use super::*;
+
+    fn fibonacci(memo: &mut Memo<u64, u64>, n: u64) -> u64 {
+        memo.get(n, |memo, &n| match n {
+            0 | 1 => n,
+            n => fibonacci(memo, n - 1) + fibonacci(memo, n - 2),
+        })
+    }
+
+    #[test]
+    fn test_get() {
+        let mut memo = Memo::new();
+
+        assert_eq!(fibonacci(&mut memo, 90), 2880067194370816120);
+        assert_eq!(
+            memo.stats(),
+            Stats {
+                hits: 88,
+                misses: 91,
+                entries: 91
+            }
+        );
+
+        fibonacci(&mut memo, 90);
+        assert_eq!(memo.stats().hits, 89);
+
+        memo.clear();
+        assert_eq!(memo.stats(), Stats::default());
+    }
+
+    fn checked(memo: &mut Memo<u32, u32>, n: u32) -> Result<u32> {
+        memo.try_get(n, |memo, &n| {
+            if n == 0 {
+                return Ok(1);
+            }
+            let half = checked(memo, n - 1)?;
+            if n > 5 {
+                bail!("{n} is too large");
+            }
+            Ok(half * 2)
+        })
+    }
+
+    #[test]
+    fn test_try_get() {
+        let mut memo = Memo::new();
+
+        assert_eq!(checked(&mut memo, 5).unwrap(), 32);
+        assert!(checked(&mut memo, 6).is_err());
+        assert_eq!(memo.stats().entries, 6);
+        assert_eq!(memo.stats().hits, 1);
+    }
+
+    #[test]
+    fn test_display() {
+        let stats = Stats {
+            hits: 1,
+            misses: 3,
+            entries: 3,
+        };
+
+        assert_eq!(
+            stats.to_string(),
+            "1 hits, 3 misses, 3 entries (25.0% hit rate)"
+        );
+    }
+}
@@ -0,0 +1,169 @@
+//! Small nom combinators for the shapes puzzle inputs keep coming in, like the
+//! `seeds: 79 14 55 13` line of 2023 day 5.
+//!
+//! Parsers are built from these and run with [`finish`], which insists on
+//! consuming the whole input and turns nom errors into readable ones.
+
+use anyhow::{anyhow, Result};
+use nom::character::complete::{char, multispace0, space0, space1, u64};
+use nom::combinator::all_consuming;
+use nom::error::Error;
+use nom::multi::separated_list0;
+use nom::sequence::{pair, preceded, separated_pair, terminated};
+use nom::{IResult, Parser};
+
+/// Whitespace separated items, leading whitespace is skipped.
+pub fn list<'a, O, F>(item: F) -> impl FnMut(&'a str) -> IResult<&'a str, Vec<O>>
+where
+    F: Parser<&'a str, O, Error<&'a str>>,
+{
+    preceded(space0, separated_list0(space1, item))
+}
+
+/// Whitespace separated unsigned integers, like `41 48 83  6`.
+pub fn unsigned_list(input: &str) -> IResult<&str, Vec<u64>> {
+    list(u64)(input)
+}
+
+/// `key: value`, any spaces after the colon are skipped.
+pub fn key_value<'a, K, V, FK, FV>(
+    key: FK,
+    value: FV,
+) -> impl FnMut(&'a str) -> IResult<&'a str, (K, V)>
+where
+    FK: Parser<&'a str, K, Error<&'a str>>,
+    FV: Parser<&'a str, V, Error<&'a str>>,
+{
+    separated_pair(key, pair(char(':'), space0), value)
+}
+
+/// Line and column, both starting at 1, of the byte `offset` into `input`.
+fn position(input: &str, offset: usize) -> (usize, usize) {
+    let before = &input[..offset];
+    let line = before.matches('\n').count() + 1;
+    let column = before.len() - before.rfind('\n').map_or(0, |newline| newline + 1) + 1;
+
+    (line, column)
+}
+
+/// Runs `parser` on the whole of `input`, trailing whitespace aside.
+///
+/// Errors point at the line and column nom gave up on, and show the rest of
+/// that line.
+pub fn finish<'a, O, F>(parser: F, input: &'a str) -> Result<O>
+where
+    F: Parser<&'a str, O, Error<&'a str>>,
+{
+    let result = all_consuming(terminated(parser, multispace0))(input);
+
+    match result {
+        Ok((_, output)) => Ok(output),
+        Err(nom::Err::Error(error) | nom::Err::Failure(error)) => {
+            let (line, column) = position(input, input.len() - error.input.len());
+            let rest = error.input.lines().next().unwrap_or_default();
+
+            Err(anyhow!(
+                "Parse error at line {line}, column {column} ({:?}): `{rest}`",
+                error.code
+            ))
+        }
+        Err(nom::Err::Incomplete(_)) => Err(anyhow!("Parse error: incomplete input")),
+    }
+}
+
+/// Helpers for the code generated by `#[derive(AocParse)]`.
+#[doc(hidden)]
+pub mod derive {
+    use std::fmt::Display;
+    use std::str::FromStr;
+
+    use anyhow::{anyhow, bail, Result};
+
+    pub fn literal<'a>(input: &str, rest: &'a str, literal: &str) -> Result<&'a str> {
+        rest.strip_prefix(literal)
+            .ok_or_else(|| anyhow!("Expected `{literal}` at `{rest}` in `{input}`"))
+    }
+
+    /// The text of `field` up to `next`, and everything after `next`.
+    pub fn until<'a>(
+        input: &str,
+        rest: &'a str,
+        field: &str,
+        next: &str,
+    ) -> Result<(&'a str, &'a str)> {
+        rest.split_once(next)
+            .ok_or_else(|| anyhow!("Expected `{next}` after field `{field}` in `{input}`"))
+    }
+
+    pub fn field<T>(field: &str, text: &str) -> Result<T>
+    where
+        T: FromStr,
+        T::Err: Display,
+    {
+        let text = text.trim();
+
+        text.parse()
+            .map_err(|error| anyhow!("Failed to parse field `{field}` from `{text}`: {error:#}"))
+    }
+
+    pub fn items<C, T>(field: &str, text: &str, separator: &str) -> Result<C>
+    where
+        C: FromIterator<T>,
+        T: FromStr,
+        T::Err: Display,
+    {
+        text.split(separator)
+            .map(str::trim)
+            .filter(|item| !item.is_empty())
+            .map(|item| {
+                item.parse().map_err(|error| {
+                    anyhow!("Failed to parse `{item}` in field `{field}`: {error:#}")
+                })
+            })
+            .collect()
+    }
+
+    pub fn end(input: &str, rest: &str) -> Result<()> {
+        if !rest.trim().is_empty() {
+            bail!("Unexpected `{}` at the end of `{input}`", rest.trim());
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use nom::character::complete::{alpha1, u32};
+
+    use super::*;
+
+    #[test]
+    fn test_lists() {
+        assert_eq!(
+            finish(unsigned_list, " 41 48  6 31").unwrap(),
+            vec![41, 48, 6, 31]
+        );
+        assert_eq!(finish(list(u32), "").unwrap(), Vec::<u32>::new());
+        assert!(finish(unsigned_list, "1 -2").is_err());
+    }
+
+    #[test]
+    fn test_key_value() {
+        assert_eq!(
+            finish(key_value(alpha1, unsigned_list), "seeds: 79 14").unwrap(),
+            ("seeds", vec![79, 14])
+        );
+        assert!(finish(key_value(alpha1, unsigned_list), "seeds 79 14").is_err());
+    }
+
+    #[test]
+    fn test_finish_errors() {
+        let error = finish(unsigned_list, "1 2\n3 x 4").unwrap_err();
+
+        assert_eq!(
+            error.to_string(),
+            "Parse error at line 2, column 1 (Eof): `3 x 4`"
+        );
+    }
+}
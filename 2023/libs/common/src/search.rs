@@ -0,0 +1,451 @@
+//! Shortest path searches over implicit graphs, given as a successor function
+//! on some state type.
+//!
+//! All searches start from any number of states and stop at the first state
+//! `is_goal` accepts, or explore everything reachable if there is none. The
+//! returned [`Paths`] remember every cheapest way to each state they reached.
+
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, HashSet, VecDeque};
+use std::hash::Hash;
+use std::ops::Add;
+
+#[derive(Debug, Clone)]
+struct Node<S, C> {
+    state: S,
+    cost: C,
+    /// Previous states on the cheapest paths found so far.
+    parents: Vec<usize>,
+    done: bool,
+}
+
+/// States reached by a search, with their costs and predecessors.
+#[derive(Debug, Clone)]
+pub struct Paths<S, C> {
+    nodes: Vec<Node<S, C>>,
+    indices: HashMap<S, usize>,
+    goal: Option<usize>,
+}
+
+impl<S, C> Paths<S, C>
+where
+    S: Clone + Eq + Hash,
+    C: Copy + Ord + Add<Output = C> + Default,
+{
+    fn new() -> Self {
+        Self {
+            nodes: Vec::new(),
+            indices: HashMap::new(),
+            goal: None,
+        }
+    }
+
+    /// Index of a new state, `None` if it has been reached before.
+    fn insert(&mut self, state: S, cost: C, parent: Option<usize>) -> Option<usize> {
+        if self.indices.contains_key(&state) {
+            return None;
+        }
+
+        let index = self.nodes.len();
+        self.indices.insert(state.clone(), index);
+        self.nodes.push(Node {
+            state,
+            cost,
+            parents: parent.into_iter().collect(),
+            done: false,
+        });
+
+        Some(index)
+    }
+
+    /// Records the step from `parent` to `state`, returns the index of
+    /// `state` if it has to be (re)visited because the step made it cheaper.
+    fn relax(&mut self, parent: usize, state: S, cost: C) -> Option<usize> {
+        let Some(&index) = self.indices.get(&state) else {
+            return self.insert(state, cost, Some(parent));
+        };
+        let node = &mut self.nodes[index];
+
+        if cost < node.cost {
+            node.cost = cost;
+            node.parents = vec![parent];
+            node.done = false;
+            Some(index)
+        } else {
+            // States visited already still gain parents, a step that costs
+            // nothing can reach them as cheaply later on. Starts keep none, so
+            // their paths end there.
+            let start = node.parents.is_empty();
+            if cost == node.cost && !start && !node.parents.contains(&parent) {
+                node.parents.push(parent);
+            }
+            None
+        }
+    }
+
+    /// The goal the search stopped at.
+    pub fn goal(&self) -> Option<&S> {
+        self.goal.map(|index| &self.nodes[index].state)
+    }
+
+    pub fn goal_cost(&self) -> Option<C> {
+        self.goal.map(|index| self.nodes[index].cost)
+    }
+
+    /// Cost of the cheapest path found to `state`. Exact for every state the
+    /// search got past, states left at the frontier might have cheaper ones.
+    pub fn cost(&self, state: &S) -> Option<C> {
+        self.indices.get(state).map(|&index| self.nodes[index].cost)
+    }
+
+    /// Number of states reached.
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    /// One cheapest path from a start to `state`, both included.
+    pub fn path_to(&self, state: &S) -> Option<Vec<S>> {
+        let mut index = *self.indices.get(state)?;
+        let mut path = vec![self.nodes[index].state.clone()];
+
+        while let Some(&parent) = self.nodes[index].parents.first() {
+            index = parent;
+            path.push(self.nodes[index].state.clone());
+        }
+        path.reverse();
+
+        Some(path)
+    }
+
+    /// Every cheapest path from a start to `state`. There can be exponentially
+    /// many, see [`Paths::states_on_paths_to`] if their states are enough.
+    ///
+    /// Steps that cost nothing can make the parents cyclic, paths skip states
+    /// they have been through already.
+    pub fn all_paths_to(&self, state: &S) -> Vec<Vec<S>> {
+        let Some(&index) = self.indices.get(state) else {
+            return Vec::new();
+        };
+
+        let mut paths = Vec::new();
+        let mut stack = vec![vec![index]];
+
+        while let Some(reversed) = stack.pop() {
+            let last = reversed[reversed.len() - 1];
+            let parents = &self.nodes[last].parents;
+
+            if parents.is_empty() {
+                paths.push(
+                    reversed
+                        .iter()
+                        .rev()
+                        .map(|&index| self.nodes[index].state.clone())
+                        .collect(),
+                );
+                continue;
+            }
+            for &parent in parents.iter().rev() {
+                if reversed.contains(&parent) {
+                    continue;
+                }
+                let mut next = reversed.clone();
+                next.push(parent);
+                stack.push(next);
+            }
+        }
+
+        paths
+    }
+
+    /// States on any of the cheapest paths to `state`, itself included. Each
+    /// state is expanded once, so cyclic parents end the walk.
+    pub fn states_on_paths_to(&self, state: &S) -> HashSet<S> {
+        let mut seen = HashSet::new();
+        let mut stack: Vec<usize> = self.indices.get(state).copied().into_iter().collect();
+
+        while let Some(index) = stack.pop() {
+            if seen.insert(index) {
+                stack.extend(&self.nodes[index].parents);
+            }
+        }
+
+        seen.into_iter()
+            .map(|index| self.nodes[index].state.clone())
+            .collect()
+    }
+
+    /// One cheapest path to the goal.
+    pub fn path(&self) -> Option<Vec<S>> {
+        self.path_to(self.goal()?)
+    }
+
+    /// Every cheapest path to the goal.
+    pub fn all_paths(&self) -> Vec<Vec<S>> {
+        match self.goal() {
+            Some(goal) => self.all_paths_to(goal),
+            None => Vec::new(),
+        }
+    }
+}
+
+/// Breadth first search where every step costs 1.
+pub fn bfs<S, I>(
+    starts: impl IntoIterator<Item = S>,
+    mut successors: impl FnMut(&S) -> I,
+    mut is_goal: impl FnMut(&S) -> bool,
+) -> Paths<S, usize>
+where
+    S: Clone + Eq + Hash,
+    I: IntoIterator<Item = S>,
+{
+    let mut paths = Paths::new();
+    let mut queue: VecDeque<usize> = starts
+        .into_iter()
+        .filter_map(|start| paths.insert(start, 0, None))
+        .collect();
+
+    while let Some(index) = queue.pop_front() {
+        let node = &mut paths.nodes[index];
+        node.done = true;
+
+        // Everything one step closer has been expanded already, so the goal
+        // has all of its parents.
+        if is_goal(&node.state) {
+            paths.goal = Some(index);
+            break;
+        }
+
+        let (state, cost) = (node.state.clone(), node.cost);
+        for next in successors(&state) {
+            queue.extend(paths.relax(index, next, cost + 1));
+        }
+    }
+
+    paths
+}
+
+/// Dijkstra's algorithm, `successors` gives the next states with the cost of
+/// getting there. Costs must not be negative.
+pub fn dijkstra<S, C, I>(
+    starts: impl IntoIterator<Item = S>,
+    successors: impl FnMut(&S) -> I,
+    is_goal: impl FnMut(&S) -> bool,
+) -> Paths<S, C>
+where
+    S: Clone + Eq + Hash,
+    C: Copy + Ord + Add<Output = C> + Default,
+    I: IntoIterator<Item = (S, C)>,
+{
+    astar(starts, successors, |_| C::default(), is_goal)
+}
+
+/// A* search, Dijkstra's algorithm guided by `heuristic`, an estimate of the
+/// cost still needed to reach a goal.
+///
+/// The estimate must never be more than the actual cost, and for all cheapest
+/// paths to be found it must not drop by more than the cost of any step.
+pub fn astar<S, C, I>(
+    starts: impl IntoIterator<Item = S>,
+    mut successors: impl FnMut(&S) -> I,
+    mut heuristic: impl FnMut(&S) -> C,
+    mut is_goal: impl FnMut(&S) -> bool,
+) -> Paths<S, C>
+where
+    S: Clone + Eq + Hash,
+    C: Copy + Ord + Add<Output = C> + Default,
+    I: IntoIterator<Item = (S, C)>,
+{
+    let mut paths = Paths::new();
+    let mut queue = BinaryHeap::new();
+
+    for start in starts {
+        let estimate = heuristic(&start);
+
+        if let Some(index) = paths.insert(start, C::default(), None) {
+            queue.push(Reverse((estimate, C::default(), index)));
+        }
+    }
+
+    while let Some(Reverse((estimate, cost, index))) = queue.pop() {
+        let node = &mut paths.nodes[index];
+        if node.done || cost > node.cost {
+            continue;
+        }
+        // Keep going while states could still be as cheap as the goal, they
+        // might be further parents of it.
+        if paths
+            .goal
+            .is_some_and(|goal| estimate > paths.nodes[goal].cost)
+        {
+            break;
+        }
+
+        let node = &mut paths.nodes[index];
+        node.done = true;
+
+        if paths.goal.is_none() && is_goal(&node.state) {
+            paths.goal = Some(index);
+        }
+
+        let state = paths.nodes[index].state.clone();
+        for (next, step) in successors(&state) {
+            let next_cost = cost + step;
+            let estimate = next_cost + heuristic(&next);
+
+            if let Some(next) = paths.relax(index, next, next_cost) {
+                queue.push(Reverse((estimate, next_cost, next)));
+            }
+        }
+    }
+
+    paths
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::grid::Grid;
+
+    use super::*;
+
+    fn maze() -> Grid<char> {
+        Grid::parse(
+            "S..#
+            .#..
+            ...E",
+        )
+        .unwrap()
+    }
+
+    fn open(grid: &Grid<char>, (row, col): (usize, usize)) -> Vec<(usize, usize)> {
+        grid.neighbours4(row, col)
+            .filter(|&cell| grid[cell] != '#')
+            .collect()
+    }
+
+    #[test]
+    fn test_bfs() {
+        let grid = maze();
+
+        let paths = bfs([(0, 0)], |&cell| open(&grid, cell), |&cell| cell == (2, 3));
+
+        assert_eq!(paths.goal(), Some(&(2, 3)));
+        assert_eq!(paths.goal_cost(), Some(5));
+        assert_eq!(paths.path().unwrap().len(), 6);
+        assert_eq!(paths.path().unwrap()[0], (0, 0));
+        // Two over the top and one along the left and bottom
+        assert_eq!(paths.all_paths().len(), 3);
+        assert_eq!(paths.states_on_paths_to(&(2, 3)).len(), 10);
+    }
+
+    #[test]
+    fn test_bfs_without_goal() {
+        let grid = maze();
+
+        let paths = bfs([(0, 0), (2, 3)], |&cell| open(&grid, cell), |_| false);
+
+        assert_eq!(paths.goal(), None);
+        assert_eq!(paths.path(), None);
+        assert_eq!(paths.len(), 10);
+        assert_eq!(paths.cost(&(1, 2)), Some(2));
+        assert_eq!(paths.cost(&(0, 3)), None);
+        assert_eq!(paths.path_to(&(1, 3)), Some(vec![(2, 3), (1, 3)]));
+    }
+
+    fn graph(node: &char) -> Vec<(char, u32)> {
+        match node {
+            'a' => vec![('b', 1), ('c', 4), ('d', 7)],
+            'b' => vec![('c', 2), ('d', 5)],
+            'c' => vec![('d', 1), ('a', 1)],
+            _ => vec![],
+        }
+    }
+
+    #[test]
+    fn test_dijkstra() {
+        let paths = dijkstra(['a'], graph, |&node| node == 'd');
+
+        assert_eq!(paths.goal_cost(), Some(4));
+        assert_eq!(paths.path(), Some(vec!['a', 'b', 'c', 'd']));
+
+        let paths = dijkstra(['a', 'c'], graph, |&node| node == 'd');
+        assert_eq!(paths.goal_cost(), Some(1));
+        assert_eq!(paths.path(), Some(vec!['c', 'd']));
+    }
+
+    #[test]
+    fn test_dijkstra_all_paths() {
+        let successors = |node: &char| match node {
+            'a' => vec![('b', 1), ('c', 2), ('e', 0)],
+            'b' => vec![('d', 2)],
+            'c' => vec![('d', 1)],
+            'e' => vec![('c', 2), ('a', 0)],
+            _ => vec![],
+        };
+
+        let paths = dijkstra(['a'], successors, |&node| node == 'd');
+
+        assert_eq!(paths.goal_cost(), Some(3));
+        assert_eq!(
+            paths.all_paths(),
+            vec![
+                vec!['a', 'b', 'd'],
+                vec!['a', 'c', 'd'],
+                vec!['a', 'e', 'c', 'd']
+            ]
+        );
+        assert!(dijkstra(['a'], successors, |&node| node == 'x')
+            .all_paths()
+            .is_empty());
+
+        // The goal is done before the cheaper looking step reaches it again
+        let successors = |node: &char| match node {
+            'a' => vec![('d', 1), ('p', 1)],
+            'p' => vec![('d', 0)],
+            _ => vec![],
+        };
+        let paths = dijkstra(['a'], successors, |&node| node == 'd');
+        assert_eq!(paths.all_paths(), vec![vec!['a', 'd'], vec!['a', 'p', 'd']]);
+
+        // Steps that cost nothing in both directions
+        let successors = |node: &char| match node {
+            'a' => vec![('x', 1), ('y', 1)],
+            'x' => vec![('y', 0), ('d', 1)],
+            'y' => vec![('x', 0)],
+            _ => vec![],
+        };
+        let paths = dijkstra(['a'], successors, |&node| node == 'd');
+        assert_eq!(
+            paths.all_paths(),
+            vec![vec!['a', 'x', 'd'], vec!['a', 'y', 'x', 'd']]
+        );
+        assert_eq!(paths.states_on_paths_to(&'d').len(), 4);
+    }
+
+    #[test]
+    fn test_astar() {
+        let grid = maze();
+        let goal = (2, 3);
+        let distance =
+            |&(row, col): &(usize, usize)| (row.abs_diff(goal.0) + col.abs_diff(goal.1)) as u32;
+        let successors = |&cell: &(usize, usize)| {
+            open(&grid, cell)
+                .into_iter()
+                .map(|next| (next, 1))
+                .collect::<Vec<_>>()
+        };
+
+        let paths = astar([(0, 0)], successors, distance, |&cell| cell == goal);
+        let guided = paths.len();
+
+        assert_eq!(paths.goal_cost(), Some(5));
+        assert_eq!(paths.all_paths().len(), 3);
+
+        let paths = dijkstra([(0, 0)], successors, |&cell| cell == goal);
+        assert_eq!(paths.goal_cost(), Some(5));
+        assert!(guided <= paths.len());
+    }
+}
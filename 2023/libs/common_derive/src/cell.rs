@@ -0,0 +1,244 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{Data, DeriveInput, Fields, Ident, LitChar, Variant};
+
+#[derive(Debug, PartialEq)]
+enum Legend {
+    Char(char),
+    Digit,
+    Other,
+}
+
+fn legend(variant: &Variant) -> syn::Result<Legend> {
+    let mut legend = None;
+
+    for attribute in variant
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("cell"))
+    {
+        let parsed = if let Ok(c) = attribute.parse_args::<LitChar>() {
+            Legend::Char(c.value())
+        } else {
+            let ident: Ident = attribute.parse_args().map_err(|error| {
+                syn::Error::new(error.span(), "Expected `'c'`, `digit` or `other`")
+            })?;
+
+            match ident.to_string().as_str() {
+                "digit" => Legend::Digit,
+                "other" => Legend::Other,
+                _ => {
+                    return Err(syn::Error::new_spanned(
+                        ident,
+                        "Expected `'c'`, `digit` or `other`",
+                    ))
+                }
+            }
+        };
+
+        if legend.replace(parsed).is_some() {
+            return Err(syn::Error::new_spanned(
+                attribute,
+                "Only one #[cell(...)] per variant",
+            ));
+        }
+    }
+
+    let legend = legend.ok_or_else(|| {
+        syn::Error::new_spanned(
+            &variant.ident,
+            "Missing #[cell('c')], #[cell(digit)] or #[cell(other)]",
+        )
+    })?;
+
+    let expected = match legend {
+        Legend::Char(_) => matches!(variant.fields, Fields::Unit),
+        Legend::Digit | Legend::Other => {
+            matches!(&variant.fields, Fields::Unnamed(fields) if fields.unnamed.len() == 1)
+        }
+    };
+    if !expected {
+        let shape = match legend {
+            Legend::Char(_) => "a unit variant",
+            Legend::Digit => "a variant with a single `u8`, like `Digit(u8)`",
+            Legend::Other => "a variant with a single `char`, like `Symbol(char)`",
+        };
+        return Err(syn::Error::new_spanned(
+            &variant.ident,
+            format!("#[cell(...)] here needs {shape}"),
+        ));
+    }
+
+    Ok(legend)
+}
+
+pub fn expand(input: &DeriveInput) -> syn::Result<TokenStream> {
+    let Data::Enum(data) = &input.data else {
+        return Err(syn::Error::new_spanned(
+            &input.ident,
+            "Cell only supports enums",
+        ));
+    };
+
+    let mut chars = Vec::new();
+    let mut digit = None;
+    let mut other = None;
+    let mut to_char = Vec::new();
+
+    for variant in &data.variants {
+        let ident = &variant.ident;
+
+        match legend(variant)? {
+            Legend::Char(c) => {
+                if chars.iter().any(|(seen, _)| *seen == c) {
+                    return Err(syn::Error::new_spanned(
+                        ident,
+                        format!("{c:?} is already used by another variant"),
+                    ));
+                }
+                chars.push((c, ident));
+                to_char.push(quote! { Self::#ident => #c });
+            }
+            Legend::Digit => {
+                if digit.replace(ident).is_some() {
+                    return Err(syn::Error::new_spanned(
+                        ident,
+                        "Only one variant can be #[cell(digit)]",
+                    ));
+                }
+                to_char.push(quote! {
+                    Self::#ident(digit) => ::std::char::from_digit(u32::from(*digit), 10).unwrap_or('?')
+                });
+            }
+            Legend::Other => {
+                if other.replace(ident).is_some() {
+                    return Err(syn::Error::new_spanned(
+                        ident,
+                        "Only one variant can be #[cell(other)]",
+                    ));
+                }
+                to_char.push(quote! { Self::#ident(c) => *c });
+            }
+        }
+    }
+
+    let name = input.ident.to_string();
+    let mut expected: Vec<String> = chars.iter().map(|(c, _)| format!("`{c}`")).collect();
+    if digit.is_some() {
+        expected.push("a digit".to_string());
+    }
+    let expected = expected.join(", ");
+
+    let char_arms = chars.iter().map(|(c, ident)| quote! { #c => Self::#ident });
+    let digit_arm = digit.map(|ident| {
+        quote! {
+            c if c.is_ascii_digit() => Self::#ident(c as u8 - b'0'),
+        }
+    });
+    let fallback = match other {
+        Some(ident) => quote! { c => Self::#ident(c) },
+        None => quote! {
+            c => ::anyhow::bail!("Unknown {} cell {c:?}, expected {}", #name, #expected)
+        },
+    };
+
+    let ident = &input.ident;
+    let (impl_generics, type_generics, where_clause) = input.generics.split_for_impl();
+
+    Ok(quote! {
+        impl #impl_generics ::common::grid::Cell for #ident #type_generics #where_clause {
+            fn from_char(c: char) -> ::anyhow::Result<Self> {
+                Ok(match c {
+                    #(#char_arms,)*
+                    #digit_arm
+                    #fallback,
+                })
+            }
+
+            fn to_char(&self) -> char {
+                match self {
+                    #(#to_char,)*
+                }
+            }
+        }
+
+        impl #impl_generics ::std::fmt::Display for #ident #type_generics #where_clause {
+            fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+                use ::std::fmt::Write;
+
+                f.write_char(::common::grid::Cell::to_char(self))
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use syn::parse_quote;
+
+    use super::*;
+
+    #[test]
+    fn test_legend() {
+        let wall: Variant = parse_quote!(
+            #[cell('#')]
+            Wall
+        );
+        let digit: Variant = parse_quote!(
+            #[cell(digit)]
+            Digit(u8)
+        );
+        let other: Variant = parse_quote!(
+            #[cell(other)]
+            Symbol(char)
+        );
+
+        assert_eq!(legend(&wall).unwrap(), Legend::Char('#'));
+        assert_eq!(legend(&digit).unwrap(), Legend::Digit);
+        assert_eq!(legend(&other).unwrap(), Legend::Other);
+    }
+
+    #[test]
+    fn test_legend_errors() {
+        let missing: Variant = parse_quote!(Wall);
+        let unknown: Variant = parse_quote!(
+            #[cell(letter)]
+            Letter(char)
+        );
+        let fields: Variant = parse_quote!(
+            #[cell('#')]
+            Wall(u8)
+        );
+        let unit_digit: Variant = parse_quote!(
+            #[cell(digit)]
+            Digit
+        );
+        let twice: Variant = parse_quote!(
+            #[cell('#')]
+            #[cell('.')]
+            Wall
+        );
+
+        for variant in [missing, unknown, fields, unit_digit, twice] {
+            assert!(legend(&variant).is_err());
+        }
+    }
+
+    #[test]
+    fn test_expand_errors() {
+        let duplicate: DeriveInput = parse_quote! {
+            enum Tile {
+                #[cell('#')]
+                Wall,
+                #[cell('#')]
+                Rock,
+            }
+        };
+        let not_enum: DeriveInput = parse_quote! {
+            struct Tile;
+        };
+
+        assert!(expand(&duplicate).is_err());
+        assert!(expand(&not_enum).is_err());
+    }
+}
@@ -0,0 +1,60 @@
+use common::rng::Rng;
+
+const WORDS: [&str; 9] = [
+    "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
+];
+
+fn digit(rng: &mut Rng) -> char {
+    char::from(b'1' + rng.range(0..9) as u8)
+}
+
+fn letter(rng: &mut Rng) -> char {
+    char::from(b'a' + rng.range(0..26) as u8)
+}
+
+fn generate_line(rng: &mut Rng) -> String {
+    let len = rng.range(3..24) as usize;
+    // Part 1 needs at least one plain digit in every line
+    let digit_at = rng.index(len);
+
+    let mut line = String::new();
+    for i in 0..len {
+        if i == digit_at || rng.chance(0.1) {
+            line.push(digit(rng));
+        } else if rng.chance(0.1) {
+            line.push_str(rng.choose(&WORDS));
+        } else {
+            line.push(letter(rng));
+        }
+    }
+
+    line
+}
+
+/// Generates `size` calibration lines mixing letters, digits and spelled out digits.
+pub fn generate(size: usize, seed: u64) -> String {
+    let mut rng = Rng::new(seed);
+
+    (0..size)
+        .map(|_| generate_line(&mut rng))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_generate() {
+        let input = generate(100, 1);
+
+        assert_eq!(input, generate(100, 1));
+        assert_ne!(input, generate(100, 2));
+        assert_eq!(input.lines().count(), 100);
+
+        let input = crate::parse(&input).unwrap();
+        assert!(crate::part1(&input).is_ok());
+        assert!(crate::part2(&input).is_ok());
+    }
+}
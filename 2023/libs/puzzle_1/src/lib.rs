@@ -1,32 +1,76 @@
-mod part1;
-mod part2;
+pub mod generator;
+pub mod part1;
+pub mod part2;
 
-use std::fs;
-
-use anyhow::{Context, Result};
+use anyhow::Result;
 use clap::Parser;
 use log::info;
 
-#[derive(Parser, Debug)]
-pub struct Command {}
+/// The calibration document, one calibration value per line.
+pub type Input = String;
 
-impl common::CommandRunner for Command {
-    fn run(&self) -> Result<()> {
-        info!("Puzzle 1");
+pub fn parse(input: &str) -> Result<Input> {
+    Ok(input.to_string())
+}
+
+pub fn part1(input: &Input) -> Result<u32> {
+    part1::sum_calibration_values(input)
+}
+
+pub fn part2(input: &Input) -> Result<u32> {
+    part2::sum_calibration_values(input)
+}
+
+pub struct Solver;
+
+impl common::Puzzle for Solver {
+    type Input = Input;
+
+    const YEAR: u16 = 2023;
+    const DAY: u8 = 1;
+    const TITLE: &'static str = "Trebuchet?!";
+
+    fn parse(input: &str) -> Result<Input> {
+        parse(input)
+    }
 
-        let input_file = "data/puzzle_1/input.txt";
+    fn part1(input: &Input) -> Result<String> {
+        part1(input).map(|answer| answer.to_string())
+    }
 
-        let input = fs::read_to_string(input_file)
-            .with_context(|| format!("Failed to read input file {input_file}"))?;
+    fn part2(input: &Input) -> Result<String> {
+        part2(input).map(|answer| answer.to_string())
+    }
 
-        let part1_sum = part1::sum_calibration_values(input.as_str())?;
+    fn generate(size: usize, seed: u64) -> String {
+        generator::generate(size, seed)
+    }
+}
 
-        println!("Part 1: {part1_sum}");
+#[derive(Parser, Debug)]
+pub struct Command {
+    /// Run a named solution variant instead of the default one
+    #[arg(long)]
+    variant: Option<String>,
+}
 
-        let part2_sum = part2::sum_calibration_values(input.as_str())?;
+impl common::CommandRunner for Command {
+    fn run(&self) -> Result<()> {
+        info!("Puzzle 1");
 
-        println!("Part 2: {part2_sum}");
+        common::run::<Solver>(self.variant.as_deref())?;
 
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fixtures() {
+        common::puzzle::check_fixtures::<Solver>(concat!(env!("CARGO_MANIFEST_DIR"), "/fixtures"))
+            .unwrap();
+    }
+}
@@ -0,0 +1,49 @@
+use common::rng::Rng;
+
+const COLORS: [&str; 3] = ["red", "green", "blue"];
+
+fn generate_round(rng: &mut Rng) -> String {
+    let mut colors = COLORS;
+    rng.shuffle(&mut colors);
+
+    let count = rng.range(1..4) as usize;
+
+    colors[..count]
+        .iter()
+        .map(|color| format!("{} {color}", rng.range(1..21)))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Generates `size` games with one to six rounds each.
+pub fn generate(size: usize, seed: u64) -> String {
+    let mut rng = Rng::new(seed);
+
+    (1..=size)
+        .map(|id| {
+            let rounds = rng.range(1..7);
+            let rounds: Vec<_> = (0..rounds).map(|_| generate_round(&mut rng)).collect();
+
+            format!("Game {id}: {}", rounds.join("; "))
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_generate() {
+        let input = generate(100, 1);
+
+        assert_eq!(input, generate(100, 1));
+        assert_ne!(input, generate(100, 2));
+
+        let games = crate::parse(&input).unwrap();
+        assert_eq!(games.len(), 100);
+        assert_eq!(games[99].id, 100);
+        assert!(games.iter().all(|game| !game.rounds.is_empty()));
+    }
+}
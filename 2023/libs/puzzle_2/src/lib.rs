@@ -1,48 +1,88 @@
-mod part1;
-mod part2;
-mod utils;
-
-use std::fs;
+pub mod generator;
+pub mod part1;
+pub mod part2;
+pub mod utils;
 
 use anyhow::{Context, Result};
 use clap::Parser;
 use log::info;
 
-use crate::part1::calculate_result;
-use crate::part2::sum_power;
-use crate::utils::{parse_line, Cubes, Game};
+pub use crate::utils::{parse_line, Cubes, Game};
 
-#[derive(Parser, Debug)]
-pub struct Command {}
+/// The bag contents part 1 validates the games against.
+pub const MAX_CUBES: Cubes = Cubes {
+    red: 12,
+    green: 13,
+    blue: 14,
+};
 
-impl common::CommandRunner for Command {
-    fn run(&self) -> Result<()> {
-        info!("Puzzle 2");
+pub type Input = Vec<Game>;
+
+pub fn parse(input: &str) -> Result<Input> {
+    input
+        .lines()
+        .map(|line| parse_line(line).with_context(|| format!("Failed to parse {line}")))
+        .collect()
+}
+
+pub fn part1(input: &Input) -> Result<u32> {
+    Ok(part1::calculate_result(input, &MAX_CUBES))
+}
+
+pub fn part2(input: &Input) -> Result<u32> {
+    Ok(part2::sum_power(input))
+}
+
+pub struct Solver;
+
+impl common::Puzzle for Solver {
+    type Input = Input;
+
+    const YEAR: u16 = 2023;
+    const DAY: u8 = 2;
+    const TITLE: &'static str = "Cube Conundrum";
 
-        let input_file = "data/puzzle_2/input.txt";
+    fn parse(input: &str) -> Result<Input> {
+        parse(input)
+    }
 
-        let input = fs::read_to_string(input_file)
-            .with_context(|| format!("Failed to read input file {input_file}"))?;
+    fn part1(input: &Input) -> Result<String> {
+        part1(input).map(|answer| answer.to_string())
+    }
 
-        let games: Result<Vec<Game>> = input
-            .lines()
-            .map(|line| parse_line(line).with_context(|| format!("Failed to parse {line}")))
-            .collect();
+    fn part2(input: &Input) -> Result<String> {
+        part2(input).map(|answer| answer.to_string())
+    }
 
-        let games = games.context("Failed to parse games")?;
+    fn generate(size: usize, seed: u64) -> String {
+        generator::generate(size, seed)
+    }
+}
 
-        let max = Cubes {
-            red: 12,
-            green: 13,
-            blue: 14,
-        };
+#[derive(Parser, Debug)]
+pub struct Command {
+    /// Run a named solution variant instead of the default one
+    #[arg(long)]
+    variant: Option<String>,
+}
 
-        let part1_results = calculate_result(&games, &max);
-        println!("Part 1: {part1_results}");
+impl common::CommandRunner for Command {
+    fn run(&self) -> Result<()> {
+        info!("Puzzle 2");
 
-        let part2_results = sum_power(&games);
-        println!("Part 2: {part2_results}");
+        common::run::<Solver>(self.variant.as_deref())?;
 
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fixtures() {
+        common::puzzle::check_fixtures::<Solver>(concat!(env!("CARGO_MANIFEST_DIR"), "/fixtures"))
+            .unwrap();
+    }
+}
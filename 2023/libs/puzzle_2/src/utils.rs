@@ -1,5 +1,7 @@
+use std::str::FromStr;
+
 use anyhow::{bail, Context, Result};
-use regex::Regex;
+use common::AocParse;
 
 #[derive(Debug, PartialEq)]
 pub struct Cubes {
@@ -8,62 +10,50 @@ pub struct Cubes {
     pub blue: u32,
 }
 
-#[derive(Debug, PartialEq)]
+/// A round like `3 blue, 4 red`, colours that are not drawn count 0.
+impl FromStr for Cubes {
+    type Err = anyhow::Error;
+
+    fn from_str(round: &str) -> Result<Self> {
+        let mut cubes = Cubes {
+            red: 0,
+            green: 0,
+            blue: 0,
+        };
+
+        for draw in round
+            .split(',')
+            .map(str::trim)
+            .filter(|draw| !draw.is_empty())
+        {
+            let (count, colour) = draw
+                .split_once(' ')
+                .with_context(|| format!("Invalid draw: {draw}"))?;
+            let count = count
+                .parse()
+                .with_context(|| format!("Failed to parse count of {draw}"))?;
+
+            match colour.trim() {
+                "red" => cubes.red = count,
+                "green" => cubes.green = count,
+                "blue" => cubes.blue = count,
+                colour => bail!("Unknown colour {colour}"),
+            }
+        }
+
+        Ok(cubes)
+    }
+}
+
+#[derive(Debug, PartialEq, AocParse)]
+#[aoc(fmt = "Game {id}: {rounds:;}")]
 pub struct Game {
     pub id: u32,
     pub rounds: Vec<Cubes>,
 }
 
 pub fn parse_line(line: &str) -> Result<Game> {
-    let parts: Vec<&str> = line.split(':').collect();
-
-    if parts.len() != 2 {
-        bail!("Invalid line: {}", line);
-    }
-
-    let game_id: u32 = parts[0]
-        .trim()
-        .strip_prefix("Game ")
-        .context("Failed to parse game id")?
-        .parse()
-        .context("Failed to parse game id")?;
-
-    let parts: Vec<&str> = parts[1].split(';').collect();
-
-    let blue_re = Regex::new(r"([0-9]+) blue").unwrap();
-    let red_re = Regex::new(r"([0-9]+) red").unwrap();
-    let green_re = Regex::new(r"([0-9]+) green").unwrap();
-
-    let rounds: Vec<Cubes> = parts
-        .iter()
-        .map(|round| {
-            let blue = blue_re
-                .captures(round)
-                .and_then(|c| c.get(1))
-                .map(|m| m.as_str())
-                .and_then(|s| s.parse::<u32>().ok())
-                .unwrap_or(0);
-            let green = green_re
-                .captures(round)
-                .and_then(|c| c.get(1))
-                .map(|m| m.as_str())
-                .and_then(|s| s.parse::<u32>().ok())
-                .unwrap_or(0);
-            let red = red_re
-                .captures(round)
-                .and_then(|c| c.get(1))
-                .map(|m| m.as_str())
-                .and_then(|s| s.parse::<u32>().ok())
-                .unwrap_or(0);
-
-            Cubes { red, green, blue }
-        })
-        .collect();
-
-    Ok(Game {
-        id: game_id,
-        rounds,
-    })
+    line.parse()
 }
 
 #[cfg(test)]
@@ -183,4 +173,20 @@ mod tests {
             assert_eq!(actual, expected);
         }
     }
+
+    #[test]
+    fn test_parse_line_errors() {
+        let error = parse_line("Game x: 3 blue").unwrap_err();
+        assert!(error
+            .to_string()
+            .starts_with("Failed to parse field `id` from `x`"));
+
+        let error = parse_line("Game 1: 3 blue; 2 pink").unwrap_err();
+        assert!(error
+            .to_string()
+            .starts_with("Failed to parse `2 pink` in field `rounds`: Unknown colour pink"));
+
+        assert!(parse_line("Round 1: 3 blue").is_err());
+        assert!(parse_line("Game 1 3 blue").is_err());
+    }
 }
@@ -0,0 +1,58 @@
+use common::rng::Rng;
+
+const SYMBOLS: [char; 10] = ['*', '#', '+', '$', '/', '=', '%', '@', '&', '-'];
+
+fn generate_row(rng: &mut Rng, width: usize) -> String {
+    let mut row = String::with_capacity(width);
+
+    while row.len() < width {
+        let remaining = width - row.len();
+
+        if rng.chance(0.12) {
+            let digits = (rng.range(1..4) as usize).min(remaining);
+
+            row.push(char::from(b'1' + rng.range(0..9) as u8));
+            for _ in 1..digits {
+                row.push(char::from(b'0' + rng.range(0..10) as u8));
+            }
+            // Keep numbers apart, so they don't merge into a longer one
+            if row.len() < width {
+                row.push('.');
+            }
+        } else if rng.chance(0.06) {
+            row.push(*rng.choose(&SYMBOLS));
+        } else {
+            row.push('.');
+        }
+    }
+
+    row
+}
+
+/// Generates a square engine schematic with `size` rows of numbers and symbols.
+pub fn generate(size: usize, seed: u64) -> String {
+    let mut rng = Rng::new(seed);
+
+    (0..size)
+        .map(|_| generate_row(&mut rng, size))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_generate() {
+        let input = generate(40, 1);
+
+        assert_eq!(input, generate(40, 1));
+        assert_ne!(input, generate(40, 2));
+
+        let engine = crate::parse(&input).unwrap();
+        assert_eq!((engine.width(), engine.height()), (40, 40));
+        assert!(crate::part1(&engine).is_ok());
+        assert!(crate::part2(&engine).is_ok());
+    }
+}
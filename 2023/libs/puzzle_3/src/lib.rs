@@ -1,41 +1,83 @@
-mod part1;
-mod part2;
-mod utils;
-
-use std::fs;
+pub mod generator;
+pub mod part1;
+pub mod part2;
+pub mod utils;
 
 use anyhow::{Context, Result};
 use clap::Parser;
 use log::info;
 
-use crate::utils::load_input;
+pub use crate::utils::{load_input, Tile};
+pub use common::grid::Grid;
 
-#[derive(Parser, Debug)]
-pub struct Command {}
+/// The engine schematic as rows of tiles.
+pub type Input = Grid<Tile>;
 
-impl common::CommandRunner for Command {
-    fn run(&self) -> Result<()> {
-        info!("Puzzle 3");
+pub fn parse(input: &str) -> Result<Input> {
+    load_input(input)
+}
+
+pub fn part1(input: &Input) -> Result<u32> {
+    let part_numbers =
+        part1::extract_part_numbers(input).context("Failed to extract part numbers")?;
+
+    Ok(part_numbers.iter().sum())
+}
+
+pub fn part2(input: &Input) -> Result<u32> {
+    part2::extract_part_numbers(input).context("Failed to extract gear ratios")
+}
+
+pub struct Solver;
+
+impl common::Puzzle for Solver {
+    type Input = Input;
+
+    const YEAR: u16 = 2023;
+    const DAY: u8 = 3;
+    const TITLE: &'static str = "Gear Ratios";
 
-        let input_file = "data/puzzle_3/input.txt";
+    fn parse(input: &str) -> Result<Input> {
+        parse(input)
+    }
 
-        let input = fs::read_to_string(input_file)
-            .with_context(|| format!("Failed to read input file {input_file}"))?;
+    fn part1(input: &Input) -> Result<String> {
+        part1(input).map(|answer| answer.to_string())
+    }
 
-        let engine = load_input(input.as_str());
+    fn part2(input: &Input) -> Result<String> {
+        part2(input).map(|answer| answer.to_string())
+    }
 
-        let part1_result: u32 = part1::extract_part_numbers(&engine)
-            .context("Failed to extract part 1")?
-            .iter()
-            .sum();
+    fn generate(size: usize, seed: u64) -> String {
+        generator::generate(size, seed)
+    }
+}
 
-        println!("Part 1: {part1_result}");
+#[derive(Parser, Debug)]
+pub struct Command {
+    /// Run a named solution variant instead of the default one
+    #[arg(long)]
+    variant: Option<String>,
+}
 
-        let part2_result =
-            part2::extract_part_numbers(&engine).context("Failed to extract part 2")?;
+impl common::CommandRunner for Command {
+    fn run(&self) -> Result<()> {
+        info!("Puzzle 3");
 
-        println!("Part 2: {part2_result}");
+        common::run::<Solver>(self.variant.as_deref())?;
 
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fixtures() {
+        common::puzzle::check_fixtures::<Solver>(concat!(env!("CARGO_MANIFEST_DIR"), "/fixtures"))
+            .unwrap();
+    }
+}
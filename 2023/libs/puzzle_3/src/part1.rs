@@ -1,98 +1,23 @@
 use anyhow::{Context, Result};
-
-fn is_symbol(c: char) -> bool {
-    !c.is_ascii_digit() && c != '.'
-}
-
-pub fn extract_part_numbers(engine: &Vec<Vec<char>>) -> Result<Vec<u32>> {
-    let mut part_numbers = Vec::new();
-
-    for x in 0..engine.len() {
-        let mut num = String::new();
-        let mut is_part = false;
-
-        for y in 0..engine[x].len() {
-            let c: char = engine[x][y];
-
-            if c.is_ascii_digit() {
-                num.push(c);
-
-                if !is_part {
-                    // above
-                    if x > 0 {
-                        // left
-                        if y > 0 && is_symbol(engine[x - 1][y - 1]) {
-                            is_part = true;
-                            continue;
-                        }
-                        // center
-                        if is_symbol(engine[x - 1][y]) {
-                            is_part = true;
-                            continue;
-                        }
-                        //right
-                        if y + 1 < engine[x].len() && is_symbol(engine[x - 1][y + 1]) {
-                            is_part = true;
-                            continue;
-                        }
-                    }
-
-                    // same line
-                    // left
-                    if y > 0 && is_symbol(engine[x][y - 1]) {
-                        is_part = true;
-                        continue;
-                    }
-                    // center
-                    // if is_symbol(engine[x][y]) {
-                    //     is_part = true;
-                    // }
-                    // right
-                    if y + 1 < engine[x].len() && is_symbol(engine[x][y + 1]) {
-                        is_part = true;
-                        continue;
-                    }
-
-                    // below
-                    if x + 1 < engine.len() {
-                        // left
-                        if y > 0 && is_symbol(engine[x + 1][y - 1]) {
-                            is_part = true;
-                            continue;
-                        }
-                        // center
-                        if is_symbol(engine[x + 1][y]) {
-                            is_part = true;
-                            continue;
-                        }
-                        // right
-                        if y + 1 < engine[x].len() && is_symbol(engine[x + 1][y + 1]) {
-                            is_part = true;
-                            continue;
-                        }
-                    }
-                }
-            } else {
-                if is_part && !num.is_empty() {
-                    let parsed: u32 = num
-                        .parse()
-                        .with_context(|| format!("Not a number: {num} ({c}: {x}/{y})"))?;
-                    part_numbers.push(parsed);
-                }
-
-                num = String::new();
-                is_part = false;
-            }
-        }
-        if is_part && !num.is_empty() {
-            let parsed: u32 = num
-                .parse()
-                .with_context(|| format!("Not a number: {num} (_: {x}/$)"))?;
-            part_numbers.push(parsed);
-        }
-    }
-
-    Ok(part_numbers)
+use common::grid::Grid;
+
+use crate::utils::Tile;
+
+pub fn extract_part_numbers(engine: &Grid<Tile>) -> Result<Vec<u32>> {
+    engine
+        .numbers_by(Tile::digit)?
+        .into_iter()
+        .filter(|number| {
+            number
+                .adjacent(engine)
+                .into_iter()
+                .any(|cell| engine[cell].is_symbol())
+        })
+        .map(|number| {
+            u32::try_from(number.value)
+                .with_context(|| format!("Part number {} is too large", number.value))
+        })
+        .collect()
 }
 
 #[cfg(test)]
@@ -101,7 +26,7 @@ mod test {
 
     use super::*;
 
-    fn test_data() -> Vec<Vec<char>> {
+    fn test_data() -> Grid<Tile> {
         let input = "467..114..
         ...*......
         ..35..633.
@@ -116,7 +41,7 @@ mod test {
         *..1.1.1.1
         1........*";
 
-        load_input(input)
+        load_input(input).unwrap()
     }
 
     #[test]
@@ -1,8 +1,35 @@
-pub fn load_input(input: &str) -> Vec<Vec<char>> {
-    input
-        .lines()
-        .map(|line| line.trim().chars().collect())
-        .collect()
+use anyhow::Result;
+use common::grid::{Cell, Grid};
+
+/// A cell of the engine schematic.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Cell)]
+pub enum Tile {
+    #[cell('.')]
+    Empty,
+    #[cell(digit)]
+    Digit(u8),
+    #[cell('*')]
+    Gear,
+    #[cell(other)]
+    Symbol(char),
+}
+
+impl Tile {
+    pub fn digit(&self) -> Option<u8> {
+        match self {
+            Self::Digit(digit) => Some(*digit),
+            _ => None,
+        }
+    }
+
+    /// Gears are symbols as well, anything but digits and `.` is.
+    pub fn is_symbol(&self) -> bool {
+        matches!(self, Self::Gear | Self::Symbol(_))
+    }
+}
+
+pub fn load_input(input: &str) -> Result<Grid<Tile>> {
+    Grid::parse_cells(input)
 }
 
 #[cfg(test)]
@@ -15,14 +42,21 @@ mod test {
             def
             ghi";
 
-        let actual = load_input(input);
+        let actual = load_input(input).unwrap();
 
-        let expected = vec![
-            vec!['a', 'b', 'c'],
-            vec!['d', 'e', 'f'],
-            vec!['g', 'h', 'i'],
-        ];
+        assert_eq!(actual.to_string(), "abc\ndef\nghi");
+    }
+
+    #[test]
+    fn test_tiles() {
+        let actual = load_input("4.*\n#.9").unwrap();
 
-        assert_eq!(actual, expected);
+        assert_eq!(actual[(0, 0)], Tile::Digit(4));
+        assert_eq!(actual[(0, 1)], Tile::Empty);
+        assert_eq!(actual[(0, 2)], Tile::Gear);
+        assert_eq!(actual[(1, 0)], Tile::Symbol('#'));
+        assert!(actual[(0, 2)].is_symbol() && actual[(1, 0)].is_symbol());
+        assert!(!actual[(1, 2)].is_symbol());
+        assert_eq!(actual.to_string(), "4.*\n#.9");
     }
 }
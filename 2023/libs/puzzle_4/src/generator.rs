@@ -0,0 +1,61 @@
+use common::rng::Rng;
+
+const WINNING_NUMBERS: usize = 10;
+const NUMBERS: usize = 25;
+
+fn distinct_numbers(rng: &mut Rng, count: usize) -> Vec<u64> {
+    let mut numbers: Vec<u64> = (1..100).collect();
+    rng.shuffle(&mut numbers);
+    numbers.truncate(count);
+
+    numbers
+}
+
+fn format_numbers(numbers: &[u64]) -> String {
+    numbers
+        .iter()
+        .map(|num| format!("{num:>2}"))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Generates `size` scratchcards with 10 winning numbers and 25 numbers each.
+pub fn generate(size: usize, seed: u64) -> String {
+    let mut rng = Rng::new(seed);
+    let width = size.to_string().len();
+
+    (1..=size)
+        .map(|id| {
+            let winning_numbers = distinct_numbers(&mut rng, WINNING_NUMBERS);
+            let numbers = distinct_numbers(&mut rng, NUMBERS);
+
+            format!(
+                "Card {id:>width$}: {} | {}",
+                format_numbers(&winning_numbers),
+                format_numbers(&numbers)
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_generate() {
+        let input = generate(100, 1);
+
+        assert_eq!(input, generate(100, 1));
+        assert_ne!(input, generate(100, 2));
+
+        let cards = crate::parse(&input).unwrap();
+        assert_eq!(cards.len(), 100);
+        assert!(cards
+            .iter()
+            .all(|card| card.winning_numbers.len() == 10 && card.numbers.len() == 25));
+        assert!(crate::part1(&cards).is_ok());
+        assert!(crate::part2(&cards).is_ok());
+    }
+}
@@ -1,44 +1,108 @@
-mod part1;
-mod part2;
-mod utils;
+pub mod generator;
+pub mod part1;
+pub mod part2;
+pub mod utils;
 
 use std::fs;
 
 use anyhow::{Context, Result};
 use clap::Parser;
+use common::puzzle::input_file;
 use log::info;
 
-use crate::{
-    part2::{count_all_wins, get_all_new_cards},
-    utils::*,
-};
+pub use crate::utils::{parse_line, Card};
+
+pub type Input = Vec<Card>;
+
+pub fn parse(input: &str) -> Result<Input> {
+    input
+        .lines()
+        .map(|line| parse_line(line).with_context(|| format!("Failed to parse {line}")))
+        .collect()
+}
+
+pub fn part1(input: &Input) -> Result<u32> {
+    Ok(input.iter().map(part1::get_card_points).sum())
+}
+
+pub fn part2(input: &Input) -> Result<u128> {
+    let points = part2::count_all_wins(input);
+
+    part2::get_all_new_cards(&points)
+}
+
+/// Counts the cards by recursing into the won ones, with cached totals.
+///
+/// Recurses as deep as the longest chain of wins, which long inputs take past
+/// the stack, so this is only a reference for small ones and not a variant.
+pub fn part2_recursive(input: &Input) -> Result<u128> {
+    let points = part2::count_all_wins(input);
+
+    part2::get_all_new_cards_recursive(&points)
+}
+
+pub struct Solver;
+
+impl common::Puzzle for Solver {
+    type Input = Input;
+
+    const YEAR: u16 = 2023;
+    const DAY: u8 = 4;
+    const TITLE: &'static str = "Scratchcards";
+
+    fn parse(input: &str) -> Result<Input> {
+        parse(input)
+    }
+
+    fn part1(input: &Input) -> Result<String> {
+        part1(input).map(|answer| answer.to_string())
+    }
+
+    fn part2(input: &Input) -> Result<String> {
+        part2(input).map(|answer| answer.to_string())
+    }
+
+    fn generate(size: usize, seed: u64) -> String {
+        generator::generate(size, seed)
+    }
+}
 
 #[derive(Parser, Debug)]
-pub struct Command {}
+pub struct Command {
+    /// Run a named solution variant instead of the default one
+    #[arg(long)]
+    variant: Option<String>,
+
+    /// Print the number of copies of every card after solving
+    #[arg(long)]
+    trace: bool,
+}
 
 impl common::CommandRunner for Command {
     fn run(&self) -> Result<()> {
         info!("Puzzle 4");
 
-        let input_file = "data/puzzle_4/input.txt";
+        common::run::<Solver>(self.variant.as_deref())?;
 
-        let input = fs::read_to_string(input_file)
-            .with_context(|| format!("Failed to read input file {input_file}"))?;
+        if self.trace {
+            let path = input_file(<Solver as common::Puzzle>::DAY);
+            let input = fs::read_to_string(&path)
+                .with_context(|| format!("Failed to read input file {path}"))?;
 
-        let cards: Result<Vec<Card>> = input
-            .lines()
-            .map(|line| parse_line(line).with_context(|| format!("Failed to parse {line}")))
-            .collect();
+            print!("{}", part2::trace(&parse(&input)?)?);
+        }
 
-        let cards = cards.context("Failed to parse input")?;
-
-        let part1_results: u32 = cards.iter().map(part1::get_card_points).sum();
-        println!("Part 1: {part1_results}");
+        Ok(())
+    }
+}
 
-        let points = count_all_wins(&cards);
-        let part2_results = get_all_new_cards(&points)?;
-        println!("Part 2: {part2_results}");
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-        Ok(())
+    #[test]
+    fn test_fixtures() {
+        common::puzzle::check_fixtures::<Solver>(concat!(env!("CARGO_MANIFEST_DIR"), "/fixtures"))
+            .unwrap();
     }
 }
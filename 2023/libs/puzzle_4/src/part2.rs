@@ -1,12 +1,59 @@
+use std::fmt::Write;
+
 use crate::part1;
 use crate::utils::*;
-use anyhow::{bail, Result};
+use anyhow::{bail, Context, Result};
+use common::memo::Memo;
+use log::debug;
 
 pub fn count_all_wins(cards: &[Card]) -> Vec<u32> {
     cards.iter().map(part1::count_wins).collect()
 }
 
-fn get_new_cards(points: &[u32], current_pos: usize) -> Result<u32> {
+/// Number of copies of every card in the end, originals included.
+///
+/// A single pass suffices, as cards only win copies of the cards after them
+/// and all copies of a card are known by the time it is scratched.
+pub fn count_copies(points: &[u32]) -> Result<Vec<u128>> {
+    let mut copies = vec![1u128; points.len()];
+
+    for (index, wins) in points.iter().enumerate() {
+        let won = (index + 1)..(index + 1 + *wins as usize).min(points.len());
+
+        for next in won {
+            copies[next] = copies[next]
+                .checked_add(copies[index])
+                .with_context(|| format!("Too many copies of card {}", next + 1))?;
+        }
+    }
+
+    Ok(copies)
+}
+
+pub fn get_all_new_cards(points: &[u32]) -> Result<u128> {
+    count_copies(points)?
+        .into_iter()
+        .try_fold(0u128, |sum, copies| sum.checked_add(copies))
+        .context("Too many cards in total")
+}
+
+/// Table of the copies of every card by id, for `--trace`.
+pub fn trace(cards: &[Card]) -> Result<String> {
+    let wins = count_all_wins(cards);
+    let copies = count_copies(&wins)?;
+    let mut out = String::new();
+
+    let _ = writeln!(out, "{:>6} {:>5} {:>20}", "card", "wins", "copies");
+    for ((card, wins), copies) in cards.iter().zip(wins).zip(copies) {
+        let _ = writeln!(out, "{:>6} {wins:>5} {copies:>20}", card.id);
+    }
+
+    Ok(out)
+}
+
+/// Cards ending up in the pile for the card at `current_pos`, itself included.
+/// The copies it wins are counted through `memo`, they are won again and again.
+fn get_new_cards(points: &[u32], current_pos: usize, memo: &mut Memo<usize, u128>) -> Result<u128> {
     if current_pos > points.len() {
         return Ok(0);
     }
@@ -14,22 +61,31 @@ fn get_new_cards(points: &[u32], current_pos: usize) -> Result<u32> {
         bail!("Invalid current_pos: {current_pos}");
     }
 
-    let next_cards = points[current_pos - 1] as usize;
+    memo.try_get(current_pos, |memo, &current_pos| {
+        let next_cards = points[current_pos - 1] as usize;
 
-    let mut sum = 1;
-    for i in 1..(next_cards + 1) {
-        sum += get_new_cards(points, current_pos + i)?;
-    }
+        let mut sum: u128 = 1;
+        for i in 1..(next_cards + 1) {
+            sum = sum
+                .checked_add(get_new_cards(points, current_pos + i, memo)?)
+                .with_context(|| format!("Too many cards won by card {current_pos}"))?;
+        }
 
-    Ok(sum)
+        Ok(sum)
+    })
 }
 
-pub fn get_all_new_cards(points: &[u32]) -> Result<u32> {
-    let mut sum = 0;
+/// [`get_all_new_cards`] by recursing into the won cards, as deep as the
+/// longest chain of wins.
+pub fn get_all_new_cards_recursive(points: &[u32]) -> Result<u128> {
+    let mut memo = Memo::new();
+
+    let mut sum: u128 = 0;
     for i in 0..points.len() {
-        let result = get_new_cards(points, i + 1)?;
-        sum += result
+        let result = get_new_cards(points, i + 1, &mut memo)?;
+        sum = sum.checked_add(result).context("Too many cards in total")?;
     }
+    debug!("Card totals: {}", memo.stats());
 
     Ok(sum)
 }
@@ -78,10 +134,13 @@ mod test {
     #[test]
     fn test_get_new_cards() {
         let points = vec![1, 1, 0];
+        let mut memo = Memo::new();
 
-        assert_eq!(get_new_cards(&points, 1).unwrap(), 3);
-        assert_eq!(get_new_cards(&points, 2).unwrap(), 2);
-        assert_eq!(get_new_cards(&points, 3).unwrap(), 1);
+        assert_eq!(get_new_cards(&points, 1, &mut memo).unwrap(), 3);
+        assert_eq!(get_new_cards(&points, 2, &mut memo).unwrap(), 2);
+        assert_eq!(get_new_cards(&points, 3, &mut memo).unwrap(), 1);
+        assert_eq!(memo.stats().misses, 3);
+        assert_eq!(memo.stats().hits, 2);
     }
 
     #[test]
@@ -117,13 +176,66 @@ mod test {
         // 6: 6                                                                                                     => 1
         // ==========================================================================================================> 30
 
-        assert_eq!(get_new_cards(&points, 1).unwrap(), 15);
-        assert_eq!(get_new_cards(&points, 2).unwrap(), 7);
-        assert_eq!(get_new_cards(&points, 3).unwrap(), 4);
-        assert_eq!(get_new_cards(&points, 4).unwrap(), 2);
-        assert_eq!(get_new_cards(&points, 5).unwrap(), 1);
-        assert_eq!(get_new_cards(&points, 6).unwrap(), 1);
+        let mut memo = Memo::new();
+        assert_eq!(get_new_cards(&points, 1, &mut memo).unwrap(), 15);
+        assert_eq!(get_new_cards(&points, 2, &mut memo).unwrap(), 7);
+        assert_eq!(get_new_cards(&points, 3, &mut memo).unwrap(), 4);
+        assert_eq!(get_new_cards(&points, 4, &mut memo).unwrap(), 2);
+        assert_eq!(get_new_cards(&points, 5, &mut memo).unwrap(), 1);
+        assert_eq!(get_new_cards(&points, 6, &mut memo).unwrap(), 1);
 
         assert_eq!(get_all_new_cards(&points).unwrap(), 30);
     }
+
+    #[test]
+    fn test_copies_and_recursion() {
+        let points = count_all_wins(&cards());
+
+        assert_eq!(count_copies(&points).unwrap(), vec![1, 2, 4, 8, 14, 1]);
+        assert_eq!(get_all_new_cards_recursive(&points).unwrap(), 30);
+        assert_eq!(get_all_new_cards_recursive(&[1, 1, 0]).unwrap(), 6);
+    }
+
+    #[test]
+    fn test_many_wins() {
+        // Every card wins the next two, the recursion alone would take 2^n steps
+        let points = vec![2; 40];
+        let mut memo = Memo::new();
+
+        assert_eq!(get_new_cards(&points, 1, &mut memo).unwrap(), 267914295);
+        assert_eq!(memo.stats().misses, 40);
+    }
+
+    #[test]
+    fn test_trace() {
+        let trace = trace(&cards()).unwrap();
+        let lines: Vec<_> = trace.lines().collect();
+
+        assert_eq!(lines.len(), 7);
+        assert_eq!(lines[0], "  card  wins               copies");
+        assert_eq!(lines[5], "     5     0                   14");
+    }
+
+    #[test]
+    fn test_large_counts() {
+        // Every card wins all cards after it, doubling the copies each card
+        let all_after = |cards: u32| (0..cards).rev().collect::<Vec<_>>();
+
+        let points = all_after(40);
+        assert_eq!(get_all_new_cards(&points).unwrap(), (1 << 40) - 1);
+        assert_eq!(get_all_new_cards_recursive(&points).unwrap(), (1 << 40) - 1);
+        assert_eq!(get_all_new_cards(&all_after(128)).unwrap(), u128::MAX);
+
+        let error = get_all_new_cards(&all_after(130)).unwrap_err();
+        assert_eq!(error.to_string(), "Too many copies of card 129");
+    }
+
+    #[test]
+    fn test_long_chain() {
+        // Far deeper than the recursion could go on the stack
+        let points = vec![1; 1_000_000];
+
+        assert_eq!(count_copies(&points).unwrap()[20], 21);
+        assert_eq!(get_all_new_cards(&points).unwrap(), 500_000_500_000);
+    }
 }
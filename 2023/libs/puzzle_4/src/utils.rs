@@ -1,8 +1,10 @@
 use std::collections::HashSet;
 
-use anyhow::{bail, Context, Result};
+use anyhow::Result;
+use common::AocParse;
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, AocParse)]
+#[aoc(fmt = "Card {id}: {winning_numbers: } | {numbers: }")]
 pub struct Card {
     pub id: u32,
     pub numbers: Vec<u32>,
@@ -10,54 +12,7 @@ pub struct Card {
 }
 
 pub fn parse_line(line: &str) -> Result<Card> {
-    let parts: Vec<&str> = line.split(':').collect();
-
-    if parts.len() != 2 {
-        bail!("Invalid line: {}", line);
-    }
-
-    let id: u32 = parts[0]
-        .trim()
-        .strip_prefix("Card")
-        .context("Failed to parse card id")?
-        .trim()
-        .parse()
-        .context("Failed to parse card id")?;
-
-    let parts: Vec<&str> = parts[1].split('|').collect();
-
-    if parts.len() != 2 {
-        bail!("Invalid line: {}", line);
-    }
-
-    let winning_numbers: Result<HashSet<u32>> = parts[0]
-        .trim()
-        .split(' ')
-        .map(|num| num.trim())
-        .filter(|num| !num.is_empty())
-        .map(|num| {
-            num.parse()
-                .with_context(|| format!("Failed to parse {num}"))
-        })
-        .collect();
-    let winning_numbers = winning_numbers.context("Failed to parse winning numbers")?;
-    let numbers: Result<Vec<u32>> = parts[1]
-        .trim()
-        .split(' ')
-        .map(|num| num.trim())
-        .filter(|num| !num.is_empty())
-        .map(|num| {
-            num.parse()
-                .with_context(|| format!("Failed to parse {num}"))
-        })
-        .collect();
-    let numbers = numbers.context("Failed to parse numbers")?;
-
-    Ok(Card {
-        id,
-        numbers,
-        winning_numbers,
-    })
+    line.parse()
 }
 
 #[cfg(test)]
@@ -113,4 +68,14 @@ mod tests {
             assert_eq!(actual, expected);
         }
     }
+
+    #[test]
+    fn test_parse_line_errors() {
+        let error = parse_line("Card 1: 41 x | 83").unwrap_err();
+
+        assert!(error
+            .to_string()
+            .starts_with("Failed to parse `x` in field `winning_numbers`"));
+        assert!(parse_line("Card 1: 41 48 83").is_err());
+    }
 }
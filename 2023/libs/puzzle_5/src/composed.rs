@@ -0,0 +1,234 @@
+use std::fmt::{self, Display};
+
+use common::interval::{Interval, IntervalSet};
+
+use crate::utils::{Id, Range};
+
+/// Values of `source` map to `value + offset`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Piece {
+    pub source: Interval<Id>,
+    pub offset: i128,
+}
+
+impl Piece {
+    pub fn destination(&self) -> Interval<Id> {
+        Interval::new(
+            shift(self.source.start, self.offset),
+            shift(self.source.end, self.offset),
+        )
+    }
+}
+
+/// Ranges only cover what they can map below [`Id::MAX`], so neither do pieces.
+fn shift(value: Id, offset: i128) -> Id {
+    Id::try_from(value as i128 + offset).expect("pieces map into Id")
+}
+
+/// Piecewise linear map over `[0, Id::MAX)`, stored as sorted, disjoint pieces.
+///
+/// Composing every stage of a pipeline into one of these turns a lookup into a
+/// single binary search.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PiecewiseMap {
+    pieces: Vec<Piece>,
+}
+
+impl Default for PiecewiseMap {
+    fn default() -> Self {
+        Self::identity()
+    }
+}
+
+impl PiecewiseMap {
+    pub fn identity() -> Self {
+        Self {
+            pieces: vec![Piece {
+                source: Interval::new(0, Id::MAX),
+                offset: 0,
+            }],
+        }
+    }
+
+    /// The map of a single stage, `ranges` sorted by source.
+    pub fn from_ranges<S: Into<Id> + Copy, D: From<Id> + From<S>>(ranges: &[Range<S, D>]) -> Self {
+        let mut pieces = Vec::new();
+        let mut rest = Some(Interval::new(0, Id::MAX));
+
+        for range in ranges {
+            let Some(current) = rest else {
+                break;
+            };
+            let source = range.source_interval();
+            let offset = range.destination as i128 - range.source as i128;
+
+            let (gap, from_range) = current.split_at(source.start);
+            pieces.extend(gap.map(|source| Piece { source, offset: 0 }));
+
+            rest = match from_range {
+                Some(from_range) => {
+                    let (inside, above) = from_range.split_at(source.end);
+                    pieces.extend(inside.map(|source| Piece { source, offset }));
+                    above
+                }
+                None => None,
+            };
+        }
+        pieces.extend(rest.map(|source| Piece { source, offset: 0 }));
+
+        Self::merged(pieces)
+    }
+
+    /// Joins neighbouring pieces with the same offset.
+    fn merged(pieces: Vec<Piece>) -> Self {
+        let mut merged: Vec<Piece> = Vec::with_capacity(pieces.len());
+
+        for piece in pieces {
+            match merged.last_mut() {
+                Some(last)
+                    if last.offset == piece.offset && last.source.end == piece.source.start =>
+                {
+                    last.source.end = piece.source.end
+                }
+                _ => merged.push(piece),
+            }
+        }
+
+        Self { pieces: merged }
+    }
+
+    /// Applies `self` first and `next` to the result.
+    pub fn then(&self, next: &PiecewiseMap) -> PiecewiseMap {
+        let mut pieces = Vec::new();
+
+        for piece in &self.pieces {
+            let destination = piece.destination();
+
+            for next_piece in &next.pieces {
+                if let Some(overlap) = destination.intersect(&next_piece.source) {
+                    pieces.push(Piece {
+                        source: Interval::new(
+                            shift(overlap.start, -piece.offset),
+                            shift(overlap.end, -piece.offset),
+                        ),
+                        offset: piece.offset + next_piece.offset,
+                    });
+                }
+            }
+        }
+        pieces.sort_by_key(|piece| piece.source.start);
+
+        Self::merged(pieces)
+    }
+
+    pub fn pieces(&self) -> &[Piece] {
+        &self.pieces
+    }
+
+    pub fn get(&self, value: Id) -> Id {
+        let index = self
+            .pieces
+            .partition_point(|piece| piece.source.end <= value);
+
+        match self.pieces.get(index) {
+            Some(piece) if piece.source.contains(value) => shift(value, piece.offset),
+            _ => value,
+        }
+    }
+
+    /// Lowest value any of the `sources` maps to.
+    pub fn min(&self, sources: &IntervalSet<Id>) -> Option<Id> {
+        sources
+            .iter()
+            .flat_map(|source| {
+                self.pieces.iter().filter_map(move |piece| {
+                    piece
+                        .source
+                        .intersect(source)
+                        .map(|overlap| (piece, overlap))
+                })
+            })
+            .map(|(piece, overlap)| shift(overlap.start, piece.offset))
+            .min()
+    }
+}
+
+impl Display for PiecewiseMap {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for piece in &self.pieces {
+            writeln!(
+                f,
+                "{} -> {} ({:+})",
+                piece.source,
+                piece.destination(),
+                piece.offset
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn seed_to_soil() -> PiecewiseMap {
+        PiecewiseMap::from_ranges(&[
+            Range::<Id, Id>::new(50, 52, 48),
+            Range::<Id, Id>::new(98, 50, 2),
+        ])
+    }
+
+    #[test]
+    fn test_from_ranges() {
+        let map = seed_to_soil();
+
+        assert_eq!(map.pieces().len(), 4);
+        assert_eq!(map.get(10), 10);
+        assert_eq!(map.get(50), 52);
+        assert_eq!(map.get(99), 51);
+        assert_eq!(map.get(100), 100);
+    }
+
+    #[test]
+    fn test_then() {
+        let soil_to_fertilizer = PiecewiseMap::from_ranges(&[
+            Range::<Id, Id>::new(0, 39, 15),
+            Range::<Id, Id>::new(15, 0, 37),
+            Range::<Id, Id>::new(52, 37, 2),
+        ]);
+
+        let composed = seed_to_soil().then(&soil_to_fertilizer);
+
+        for seed in [0, 13, 14, 49, 50, 51, 79, 97, 98, 99, 100, 1000] {
+            assert_eq!(
+                composed.get(seed),
+                soil_to_fertilizer.get(seed_to_soil().get(seed)),
+                "seed {seed}"
+            );
+        }
+        assert_eq!(PiecewiseMap::identity().then(&composed), composed);
+    }
+
+    #[test]
+    fn test_min() {
+        let sources: IntervalSet<Id> = [Interval::new(95, 100)].into_iter().collect();
+
+        assert_eq!(seed_to_soil().min(&sources), Some(50));
+    }
+
+    #[test]
+    fn test_display() {
+        let map = seed_to_soil().to_string();
+
+        assert_eq!(
+            map.lines().take(3).collect::<Vec<_>>(),
+            vec![
+                "[0, 50) -> [0, 50) (+0)",
+                "[50, 98) -> [52, 100) (+2)",
+                "[98, 100) -> [50, 52) (-48)"
+            ]
+        );
+    }
+}
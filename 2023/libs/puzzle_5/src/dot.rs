@@ -0,0 +1,162 @@
+use std::fmt::Write;
+use std::fs;
+use std::path::PathBuf;
+
+use anyhow::{Context, Result};
+use clap::Parser;
+
+use crate::pipeline::{Pipeline, Stage};
+
+fn source_node(stage: usize, range: usize) -> String {
+    format!("s{stage}_source_{range}")
+}
+
+fn destination_node(stage: usize, range: usize) -> String {
+    format!("s{stage}_destination_{range}")
+}
+
+/// `text` fit for a quoted DOT label.
+fn escape(text: &str) -> String {
+    text.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+fn write_stage(out: &mut String, index: usize, stage: &Stage) {
+    let _ = writeln!(out, "    subgraph cluster_{index} {{");
+    let _ = writeln!(out, "        label=\"{}\";", escape(&stage.name()));
+
+    for (range_index, range) in stage.ranges.iter().enumerate() {
+        let (number, _) = stage.line(range_index);
+        let offset = range.destination as i128 - range.source as i128;
+
+        let _ = writeln!(
+            out,
+            "        {} [label=\"{}\"];",
+            source_node(index, range_index),
+            escape(&format!("{} {}", stage.from, range.source_interval()))
+        );
+        let _ = writeln!(
+            out,
+            "        {} [label=\"{}\"];",
+            destination_node(index, range_index),
+            escape(&format!("{} {}", stage.to, range.destination_interval()))
+        );
+        let _ = writeln!(
+            out,
+            "        {} -> {} [label=\"{}\"];",
+            source_node(index, range_index),
+            destination_node(index, range_index),
+            escape(&format!("{offset:+} (line {number})"))
+        );
+    }
+
+    let _ = writeln!(out, "    }}");
+}
+
+/// The maps as a DOT graph, one cluster per stage.
+///
+/// Every range is an edge from its source to its destination interval, labelled
+/// with the offset. Dashed edges lead from a destination interval to the source
+/// intervals of the next stage it overlaps, which is where intervals get split.
+/// Values outside of all ranges map to themselves and are left out.
+pub fn render(pipeline: &Pipeline) -> String {
+    let mut out = String::new();
+
+    let _ = writeln!(out, "digraph almanac {{");
+    let _ = writeln!(out, "    rankdir=LR;");
+    let _ = writeln!(out, "    node [shape=box];");
+
+    for (index, stage) in pipeline.stages().iter().enumerate() {
+        write_stage(&mut out, index, stage);
+    }
+
+    for (index, stages) in pipeline.stages().windows(2).enumerate() {
+        let (stage, next) = (&stages[0], &stages[1]);
+
+        for (range_index, range) in stage.ranges.iter().enumerate() {
+            let destination = range.destination_interval();
+
+            for (next_index, next_range) in next.ranges.iter().enumerate() {
+                if let Some(overlap) = destination.intersect(&next_range.source_interval()) {
+                    let _ = writeln!(
+                        out,
+                        "    {} -> {} [style=dashed, label=\"{}\"];",
+                        destination_node(index, range_index),
+                        source_node(index + 1, next_index),
+                        escape(&overlap.to_string())
+                    );
+                }
+            }
+        }
+    }
+
+    let _ = writeln!(out, "}}");
+
+    out
+}
+
+/// Export the maps as a Graphviz DOT graph, render it with `dot -Tsvg`
+#[derive(Parser, Debug)]
+pub struct Command {
+    /// Read the almanac from this file instead of the puzzle input
+    #[arg(short, long)]
+    input: Option<PathBuf>,
+
+    /// Write the graph to this file instead of stdout
+    #[arg(short, long)]
+    output: Option<PathBuf>,
+}
+
+impl common::CommandRunner for Command {
+    fn run(&self) -> Result<()> {
+        let input = crate::read_input(self.input.as_deref())?;
+        let dot = render(&input.pipeline);
+
+        match &self.output {
+            Some(output) => fs::write(output, dot)
+                .with_context(|| format!("Failed to write {}", output.display()))?,
+            None => print!("{dot}"),
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render() {
+        let pipeline = Pipeline::parse(&[
+            "",
+            "seed-to-soil map:",
+            "50 98 2",
+            "52 50 48",
+            "",
+            "soil-to-fertilizer map:",
+            "0 15 37",
+        ])
+        .unwrap();
+
+        let dot = render(&pipeline);
+
+        assert!(dot.starts_with("digraph almanac {\n"));
+        assert!(dot.contains("        label=\"seed-to-soil\";\n"));
+        assert!(dot.contains("s0_source_0 [label=\"seed [50, 98)\"];"));
+        assert!(dot.contains("s0_source_1 -> s0_destination_1 [label=\"-48 (line 3)\"];"));
+        assert!(dot.contains("s0_destination_1 -> s1_source_0 [style=dashed, label=\"[50, 52)\"];"));
+        assert!(!dot.contains("s0_destination_0 -> s1_source_0"));
+        assert!(dot.ends_with("}\n"));
+    }
+
+    #[test]
+    fn test_render_escapes_labels() {
+        let pipeline = Pipeline::parse(&["", "se\"ed-to-so\\il map:", "50 98 2"]).unwrap();
+
+        let dot = render(&pipeline);
+
+        assert!(dot.contains("        label=\"se\\\"ed-to-so\\\\il\";\n"));
+        assert!(dot.contains("s0_source_0 [label=\"se\\\"ed [98, 100)\"];"));
+        assert!(dot.contains("s0_destination_0 [label=\"so\\\\il [50, 52)\"];"));
+    }
+}
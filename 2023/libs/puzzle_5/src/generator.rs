@@ -0,0 +1,80 @@
+use common::rng::Rng;
+
+use crate::utils::Id;
+
+const MAPS: [&str; 7] = [
+    "seed-to-soil",
+    "soil-to-fertilizer",
+    "fertilizer-to-water",
+    "water-to-light",
+    "light-to-temperature",
+    "temperature-to-humidity",
+    "humidity-to-location",
+];
+
+const SEED_RANGES: usize = 10;
+const MAX_ID: Id = 1 << 32;
+
+/// `size` non overlapping mapping lines, leaving random gaps as identity.
+fn generate_map(rng: &mut Rng, size: usize) -> Vec<String> {
+    let mut cuts: Vec<Id> = (0..size * 2).map(|_| rng.range(0..MAX_ID)).collect();
+    cuts.sort();
+    cuts.dedup();
+
+    let mut lines: Vec<_> = cuts
+        .chunks_exact(2)
+        .filter(|pair| pair[1] > pair[0])
+        .map(|pair| {
+            let source = pair[0];
+            let length = pair[1] - pair[0];
+            let destination = rng.range(0..MAX_ID - length);
+
+            format!("{destination} {source} {length}")
+        })
+        .collect();
+    rng.shuffle(&mut lines);
+
+    lines
+}
+
+/// Generates an almanac with 10 seed ranges and `size` mapping lines per map.
+pub fn generate(size: usize, seed: u64) -> String {
+    let mut rng = Rng::new(seed);
+    let max_length = 10_000 * size.max(1) as Id;
+
+    let seeds: Vec<_> = (0..SEED_RANGES)
+        .map(|_| {
+            let length = rng.range(1..max_length);
+            let start = rng.range(0..MAX_ID - length);
+
+            format!("{start} {length}")
+        })
+        .collect();
+
+    let mut blocks = vec![format!("seeds: {}", seeds.join(" "))];
+
+    for map in MAPS {
+        let lines = generate_map(&mut rng, size);
+        blocks.push(format!("{map} map:\n{}", lines.join("\n")));
+    }
+
+    blocks.join("\n\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_generate() {
+        let input = generate(20, 1);
+
+        assert_eq!(input, generate(20, 1));
+        assert_ne!(input, generate(20, 2));
+
+        let input = crate::parse(&input).unwrap();
+        assert_eq!(input.seeds.len(), 20);
+        assert_eq!(input.seed_ranges.len(), 10);
+        assert!(crate::part1(&input).is_ok());
+    }
+}
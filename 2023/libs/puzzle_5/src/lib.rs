@@ -1,49 +1,447 @@
-mod part1;
-mod part2;
-mod utils;
+pub mod composed;
+pub mod dot;
+pub mod generator;
+pub mod part1;
+pub mod part2;
+pub mod pipeline;
+pub mod trace;
+pub mod utils;
+pub mod validate;
 
 use std::fs;
+use std::path::{Path, PathBuf};
 
-use anyhow::{Context, Result};
-use clap::Parser;
+use anyhow::{bail, Context, Result};
+use clap::{Parser, Subcommand};
+use common::interval::IntervalSet;
+use common::puzzle::input_file;
+use common::section::Dispatcher;
+use common::Variant;
 use log::info;
 
-use crate::part1::PlantDetails;
+pub use crate::composed::{Piece, PiecewiseMap};
+pub use crate::part1::PlantDetails;
+pub use crate::part2::SeedRange;
+pub use crate::pipeline::{Pipeline, Stage};
+pub use crate::utils::{
+    Almanac, Fertilizer, Humidity, Id, Light, Location, Range, Seed, Soil, Temperature, Water,
+};
+pub use crate::validate::{Issue, IssueKind, Validation};
+
+#[derive(Debug, PartialEq)]
+pub struct Input {
+    pub seeds: Vec<Seed>,
+    pub seed_ranges: Vec<SeedRange>,
+    pub pipeline: Pipeline,
+}
+
+impl Input {
+    /// The maps as typed [`Almanac`], fails if they are not the usual seed to location chain.
+    pub fn almanac(&self) -> Result<Almanac> {
+        Almanac::try_from(&self.pipeline)
+    }
+}
+
+pub fn parse(input: &str) -> Result<Input> {
+    parse_with(input, Validation::Lenient)
+}
+
+/// A section of the almanac.
+enum Block<'a> {
+    Seeds(&'a str),
+    Map(Stage),
+}
+
+fn seed_header(header: &str) -> Option<&str> {
+    header.starts_with("seeds:").then_some(header)
+}
+
+pub fn parse_with(input: &str, validation: Validation) -> Result<Input> {
+    let blocks = Dispatcher::new()
+        .on(seed_header, |line, section| {
+            if !section.body.is_empty() {
+                bail!("Expected a blank line after the seeds");
+            }
+            Ok(Block::Seeds(line))
+        })
+        .on(pipeline::parse_header, |(from, to), section| {
+            Stage::parse(from, to, section).map(Block::Map)
+        })
+        .parse(input)
+        .context("Failed to parse almanac")?;
+
+    let mut seed_lines = Vec::new();
+    let mut stages = Vec::new();
+    for block in blocks {
+        match block {
+            Block::Seeds(line) => seed_lines.push(line),
+            Block::Map(stage) => stages.push(stage),
+        }
+    }
+
+    let [seed_line] = seed_lines[..] else {
+        bail!("Expected one seed line, found {}", seed_lines.len());
+    };
+
+    let pipeline = Pipeline::new(stages).context("Failed to parse almanac")?;
+    validate::check(&pipeline, validation)?;
+    let seeds = part1::extract_seeds(seed_line).context("Failed to extract seeds")?;
+    let seed_ranges =
+        part2::extract_seed_ranges(seed_line).context("Failed to extract seed ranges")?;
+
+    Ok(Input {
+        seeds,
+        seed_ranges,
+        pipeline,
+    })
+}
+
+/// Parses the given file, or the puzzle input if there is none.
+pub(crate) fn read_input(path: Option<&Path>) -> Result<Input> {
+    let path = path
+        .map(Path::to_path_buf)
+        .unwrap_or_else(|| PathBuf::from(input_file(<Solver as common::Puzzle>::DAY)));
+    let input = fs::read_to_string(&path)
+        .with_context(|| format!("Failed to read input file {}", path.display()))?;
+
+    parse(&input)
+}
+
+pub fn part1(input: &Input) -> Result<Location> {
+    input
+        .seeds
+        .iter()
+        .map(|seed| Location(input.pipeline.map(seed.0)))
+        .min()
+        .context("Failed to find lowest location")
+}
+
+pub fn part2(input: &Input) -> Result<Location> {
+    part2::get_lowest_location(&input.seed_ranges, &input.pipeline)
+}
+
+/// Walks every single seed, orders of magnitude slower than [`part2`].
+///
+/// Real inputs have billions of seeds, so this is only a reference for small
+/// ones and not one of the [`Solver`] variants.
+pub fn part2_brute_force(input: &Input) -> Result<Location> {
+    input
+        .seed_ranges
+        .iter()
+        .cloned()
+        .flat_map(|range| range.into_iter())
+        .map(|seed| Location(input.pipeline.map(seed.0)))
+        .min()
+        .context("Failed to find lowest location")
+}
+
+/// Looks the seeds up in the composed map of all stages.
+pub fn part1_composed(input: &Input) -> Result<Location> {
+    let composed = input.pipeline.compose();
+
+    input
+        .seeds
+        .iter()
+        .map(|seed| Location(composed.get(seed.0)))
+        .min()
+        .context("Failed to find lowest location")
+}
+
+/// Intersects the seed ranges with the pieces of the composed map of all stages.
+pub fn part2_composed(input: &Input) -> Result<Location> {
+    let seeds: IntervalSet<Id> = input.seed_ranges.iter().map(SeedRange::interval).collect();
+
+    input
+        .pipeline
+        .compose()
+        .min(&seeds)
+        .map(Location)
+        .context("Failed to find lowest location")
+}
+
+/// Walks the locations upwards until one is reached from a seed in the ranges.
+///
+/// Takes as many steps as the answer is large, far too slow for real inputs,
+/// but handy to double check [`part2`] on small ones.
+pub fn part2_reverse(input: &Input) -> Result<Location> {
+    if input.seed_ranges.is_empty() {
+        bail!("No seed ranges");
+    }
+
+    let seeds: IntervalSet<Id> = input.seed_ranges.iter().map(SeedRange::interval).collect();
+
+    // Seeds end up where a range moved them or where they started, so no
+    // location lies past the end of those.
+    let end = seeds
+        .iter()
+        .map(|seed| seed.end)
+        .chain(
+            input
+                .pipeline
+                .stages()
+                .iter()
+                .flat_map(|stage| &stage.ranges)
+                .map(|range| range.destination_interval().end),
+        )
+        .max()
+        .unwrap_or_default();
+
+    (0..end)
+        .map(Location)
+        .find(|location| {
+            input
+                .pipeline
+                .sources(location.0)
+                .into_iter()
+                .any(|seed| seeds.contains(seed))
+        })
+        .context("No location is reached from the seed ranges")
+}
+
+pub struct Solver;
+
+impl common::Puzzle for Solver {
+    type Input = Input;
+
+    const YEAR: u16 = 2023;
+    const DAY: u8 = 5;
+    const TITLE: &'static str = "If You Give A Seed A Fertilizer";
+
+    fn parse(input: &str) -> Result<Input> {
+        parse(input)
+    }
+
+    fn part1(input: &Input) -> Result<String> {
+        part1(input).map(|answer| answer.to_string())
+    }
+
+    fn part2(input: &Input) -> Result<String> {
+        part2(input).map(|answer| answer.to_string())
+    }
+
+    fn generate(size: usize, seed: u64) -> String {
+        generator::generate(size, seed)
+    }
+
+    fn variants() -> Vec<Variant<Input>> {
+        vec![
+            Variant {
+                part: 1,
+                name: "composed",
+                solve: |input| part1_composed(input).map(|answer| answer.to_string()),
+            },
+            Variant {
+                part: 2,
+                name: "composed",
+                solve: |input| part2_composed(input).map(|answer| answer.to_string()),
+            },
+        ]
+    }
+}
+
+/// [`Solver`] that rejects inputs with overlapping, empty or overflowing ranges.
+pub struct StrictSolver;
+
+impl common::Puzzle for StrictSolver {
+    type Input = Input;
+
+    const YEAR: u16 = Solver::YEAR;
+    const DAY: u8 = Solver::DAY;
+    const TITLE: &'static str = Solver::TITLE;
+
+    fn parse(input: &str) -> Result<Input> {
+        parse_with(input, Validation::Strict)
+    }
+
+    fn part1(input: &Input) -> Result<String> {
+        Solver::part1(input)
+    }
+
+    fn part2(input: &Input) -> Result<String> {
+        Solver::part2(input)
+    }
+
+    fn generate(size: usize, seed: u64) -> String {
+        Solver::generate(size, seed)
+    }
+
+    fn variants() -> Vec<Variant<Input>> {
+        Solver::variants()
+    }
+}
 
 #[derive(Parser, Debug)]
-pub struct Command {}
+pub struct Command {
+    /// Run a named solution variant instead of the default one
+    #[arg(long)]
+    variant: Option<String>,
+
+    /// Reject almanacs with overlapping, empty or overflowing ranges instead of warning
+    #[arg(long)]
+    strict: bool,
+
+    #[command(subcommand)]
+    command: Option<Subcommands>,
+}
+
+#[derive(Subcommand, Debug)]
+enum Subcommands {
+    Trace(trace::Command),
+    Dot(dot::Command),
+}
 
 impl common::CommandRunner for Command {
     fn run(&self) -> Result<()> {
         info!("Puzzle 5");
 
-        let input_file = "data/puzzle_5/input.txt";
+        match &self.command {
+            Some(Subcommands::Trace(command)) => return command.run(),
+            Some(Subcommands::Dot(command)) => return command.run(),
+            None => {}
+        }
 
-        let input = fs::read_to_string(input_file)
-            .with_context(|| format!("Failed to read input file {input_file}"))?;
+        if self.strict {
+            common::run::<StrictSolver>(self.variant.as_deref())?;
+        } else {
+            common::run::<Solver>(self.variant.as_deref())?;
+        }
 
-        let mut lines = input.lines();
-        let seed_line = lines.next().context("Failed to read seed line")?;
-        let almanac_lines: Vec<_> = lines.collect();
+        Ok(())
+    }
+}
 
-        let almanac = utils::parse_input(&almanac_lines).context("Failed to parse almanac")?;
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-        let part1_seeds = part1::extract_seeds(seed_line).context("Failed to extract seeds")?;
-        let part1_seed_iterator = part1_seeds.into_iter();
-        let part1_results: PlantDetails =
-            part1::get_lowest_location(part1_seed_iterator, &almanac)?;
-        let part1_location = part1_results.location;
-        println!("Part 1: {part1_location}");
+    fn input() -> &'static str {
+        "seeds: 79 14 55 13
 
-        let part2_seed_ranges =
-            part2::extract_seed_ranges(seed_line).context("Failed to extract seed ranges")?;
-        let part2_seed_iterator = part2_seed_ranges
-            .into_iter()
-            .flat_map(|range| range.into_iter());
-        let part2_results = part1::get_lowest_location(part2_seed_iterator, &almanac)?;
-        let part2_location = part2_results.location;
-        println!("Part 2: {part2_location}");
+         seed-to-soil map:
+         50 98 2
+         52 50 48
 
-        Ok(())
+         soil-to-fertilizer map:
+         0 15 37
+         37 52 2
+         39 0 15
+
+         fertilizer-to-water map:
+         49 53 8
+         0 11 42
+         42 0 7
+         57 7 4
+
+         water-to-light map:
+         88 18 7
+         18 25 70
+
+         light-to-temperature map:
+         45 77 23
+         81 45 19
+         68 64 13
+
+         temperature-to-humidity map:
+         0 69 1
+         1 0 69
+
+         humidity-to-location map:
+         60 56 37
+         56 93 4"
+            .trim()
+    }
+
+    #[test]
+    fn test_public_api() {
+        let input = parse(input()).unwrap();
+
+        assert_eq!(input.seeds, vec![Seed(79), Seed(14), Seed(55), Seed(13)]);
+        assert_eq!(part1(&input).unwrap(), Location(35));
+        assert_eq!(part2(&input).unwrap(), Location(46));
+        assert_eq!(part2_brute_force(&input).unwrap(), Location(46));
+        assert_eq!(part2_reverse(&input).unwrap(), Location(46));
+        assert_eq!(part1_composed(&input).unwrap(), Location(35));
+        assert_eq!(part2_composed(&input).unwrap(), Location(46));
+        assert!(input.almanac().is_ok());
+    }
+
+    #[test]
+    fn test_other_categories() {
+        let input = "seeds: 79 14 55 13
+
+            seed-to-dirt map:
+            50 98 2
+            52 50 48
+
+            dirt-to-location map:
+            0 80 10";
+
+        let input = parse(input).unwrap();
+
+        assert_eq!(part1(&input).unwrap(), Location(1));
+        assert_eq!(part2(&input).unwrap(), Location(1));
+        assert_eq!(part2_brute_force(&input).unwrap(), Location(1));
+        assert!(input.almanac().is_err());
+    }
+
+    #[test]
+    fn test_validation() {
+        let input = "seeds: 98 99
+
+            seed-to-soil map:
+            50 98 2
+            52 97 48";
+
+        let error = parse_with(input, Validation::Strict).unwrap_err();
+        assert!(format!("{error:#}").contains("line 4 `50 98 2`"));
+
+        let input = parse(input).unwrap();
+        assert_eq!(part1(&input).unwrap(), Location(53));
+    }
+
+    #[test]
+    fn test_overflowing_range() {
+        let input = "seeds: 5 1
+
+            seed-to-soil map:
+            18446744073709551613 0 10
+
+            soil-to-location map:
+            0 100 1";
+
+        let parsed = parse(input).unwrap();
+        assert_eq!(part1(&parsed).unwrap(), Location(5));
+        assert_eq!(part2(&parsed).unwrap(), Location(5));
+        assert_eq!(part2_brute_force(&parsed).unwrap(), Location(5));
+        assert_eq!(part2_reverse(&parsed).unwrap(), Location(5));
+        assert!(parse_with(input, Validation::Strict).is_err());
+
+        let checks = common::Entry::of::<Solver>().crosscheck(input).unwrap();
+        assert!(
+            common::puzzle::disagreements(&checks).is_empty(),
+            "{checks:?}"
+        );
+        assert!(checks
+            .iter()
+            .all(|check| check.outcome == Ok("5".to_string())));
+    }
+
+    #[test]
+    fn test_reverse_without_seeds() {
+        let input = parse(
+            "seeds: 79 0
+
+            seed-to-location map:
+            50 98 2",
+        )
+        .unwrap();
+
+        assert!(part2_reverse(&input).is_err());
+    }
+
+    #[test]
+    fn test_fixtures() {
+        common::puzzle::check_fixtures::<Solver>(concat!(env!("CARGO_MANIFEST_DIR"), "/fixtures"))
+            .unwrap();
     }
 }
@@ -2,22 +2,7 @@ use crate::utils::*;
 use anyhow::{Context, Result};
 
 pub fn extract_seeds(input: &str) -> Result<Vec<Seed>> {
-    let raw_seeds = input
-        .trim()
-        .split(':')
-        .last()
-        .context("Failed to extract seeds")?;
-
-    raw_seeds
-        .split(' ')
-        .map(|num| num.trim())
-        .filter(|num| !num.is_empty())
-        .map(|num| {
-            num.parse()
-                .map(Seed)
-                .with_context(|| format!("Failed to parse {num}"))
-        })
-        .collect()
+    Ok(extract_seed_numbers(input)?.into_iter().map(Seed).collect())
 }
 #[derive(Debug, PartialEq, Clone)]
 pub struct PlantDetails {
@@ -31,7 +16,25 @@ pub struct PlantDetails {
     pub location: Location,
 }
 
-fn get_details(almanac: &Almanac, seed: Seed) -> PlantDetails {
+impl std::fmt::Display for PlantDetails {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "seed {}, soil {}, fertilizer {}, water {}, light {}, temperature {}, humidity {}, location {}",
+            self.seed,
+            self.soil,
+            self.fertilizer,
+            self.water,
+            self.light,
+            self.temperature,
+            self.humidity,
+            self.location
+        )
+    }
+}
+
+/// Every category `seed` passes through on its way to a location.
+pub fn get_details(almanac: &Almanac, seed: Seed) -> PlantDetails {
     let soil = almanac.soil(seed);
     let fertilizer = almanac.fertilizer(soil);
     let water = almanac.water(fertilizer);
@@ -1,34 +1,43 @@
+use crate::pipeline::Pipeline;
 use crate::utils::*;
 use anyhow::{Context, Result};
+use common::interval::{Interval, IntervalSet};
 use log::debug;
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Clone)]
 pub struct SeedRange {
-    start: Id,
-    length: Id,
+    pub start: Id,
+    pub length: Id,
 }
 
-// impl<S: Into<Id>, D: From<Id> + From<S>> Range<S, D> {
-//     pub fn intersects<SS: Into<Id>>(&self, range: Range<SS, S>) -> bool
-//     where
-//         S: From<SS> + From<Id>,
-//     {
-//         (range.destination >= self.source && range.destination < self.source + self.length)
-//             || (range.destination + range.length - 1 >= self.source
-//                 && range.destination + range.length - 1 < self.source + self.length)
-//     }
-// }
+impl SeedRange {
+    pub fn interval(&self) -> Interval<Id> {
+        Interval::new(self.start, self.start.saturating_add(self.length))
+    }
+}
+
+/// Lowest location of any seed in the ranges, pushing whole ranges through the maps.
+pub fn get_lowest_location(seed_ranges: &[SeedRange], pipeline: &Pipeline) -> Result<Location> {
+    let seeds: IntervalSet<Id> = seed_ranges.iter().map(SeedRange::interval).collect();
+
+    pipeline
+        .map_intervals(&seeds)
+        .min()
+        .map(Location)
+        .context("Failed to find lowest location")
+}
 
+/// Seeds of a [`SeedRange`], ending below [`Id::MAX`] like its interval.
 pub struct SeedRangeIterator {
     current: Id,
-    stop: Id,
+    end: Id,
 }
 
 impl Iterator for SeedRangeIterator {
     type Item = Seed;
 
     fn next(&mut self) -> Option<Self::Item> {
-        if self.current > self.stop {
+        if self.current == self.end {
             return None;
         }
 
@@ -47,29 +56,22 @@ impl IntoIterator for SeedRange {
             "IntoIterator for SeedRange({}, {})",
             self.start, self.length
         );
+        let interval = self.interval();
         SeedRangeIterator {
-            current: self.start,
-            stop: self.start + self.length - 1,
+            current: interval.start,
+            end: interval.end,
         }
     }
 }
 
 pub fn extract_seed_ranges(input: &str) -> Result<Vec<SeedRange>> {
-    let raw_seeds: Vec<_> = input
-        .trim()
-        .split(':')
-        .last()
-        .context("Failed to extract seeds")?
-        .split(' ')
-        .map(|num| num.trim())
-        .filter(|num| !num.is_empty())
-        .collect();
+    let raw_seeds = extract_seed_numbers(input)?;
 
     let mut seed_ranges = Vec::new();
 
     for i in (1..raw_seeds.len()).step_by(2) {
-        let start = raw_seeds[i - 1].parse()?;
-        let length = raw_seeds[i].parse()?;
+        let start = raw_seeds[i - 1];
+        let length = raw_seeds[i];
 
         seed_ranges.push(SeedRange { start, length })
     }
@@ -117,15 +119,17 @@ mod test {
             .trim()
     }
 
-    // #[test]
-    // fn test_range_intersects() {
-    //     assert!(Range::<Seed, Soil>::new(0, 10, 10).intersects(Range::<Seed, Seed>::new(0, 9, 10)));
-    //     assert!(Range::<Seed, Soil>::new(0, 10, 1).intersects(Range::<Seed, Seed>::new(0, 0, 10)));
-    //     assert_eq!(
-    //         Range::<Seed, Soil>::new(0, 10, 1).intersects(Range::<Seed, Seed>::new(0, 9, 1)),
-    //         false
-    //     );
-    // }
+    #[test]
+    fn test_get_lowest_location() {
+        let input = input();
+        let seed_ranges = extract_seed_ranges(input.lines().next().unwrap()).unwrap();
+        let lines: Vec<_> = input.lines().skip(1).collect();
+        let pipeline = Pipeline::parse(&lines).unwrap();
+
+        let actual = get_lowest_location(&seed_ranges, &pipeline).unwrap();
+
+        assert_eq!(actual, Location(46));
+    }
 
     #[test]
     fn test_seed_range_extraction() {
@@ -163,4 +167,22 @@ mod test {
         assert_eq!(iter.next(), Some(Seed(83)));
         assert_eq!(iter.next(), None);
     }
+
+    #[test]
+    fn test_iterate_seed_range_edges() {
+        let empty = SeedRange {
+            start: 0,
+            length: 0,
+        };
+        assert_eq!(empty.into_iter().next(), None);
+
+        let last = SeedRange {
+            start: Id::MAX - 2,
+            length: 5,
+        };
+        assert_eq!(
+            last.into_iter().collect::<Vec<_>>(),
+            vec![Seed(Id::MAX - 2), Seed(Id::MAX - 1)]
+        );
+    }
 }
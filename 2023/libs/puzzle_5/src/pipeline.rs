@@ -0,0 +1,244 @@
+use anyhow::{bail, Context, Result};
+use common::interval::IntervalSet;
+use common::section::{Dispatcher, Section};
+
+use crate::composed::PiecewiseMap;
+use crate::utils::{extract_mapping, lookup, map_intervals, reverse_lookup, Id, Range};
+
+/// One `<from>-to-<to> map:` block, ranges sorted by source.
+#[derive(Debug, PartialEq)]
+pub struct Stage {
+    pub from: String,
+    pub to: String,
+    pub ranges: Vec<Range<Id, Id>>,
+    /// Line number every range was parsed from, in the same order.
+    pub lines: Vec<usize>,
+}
+
+impl Stage {
+    /// Parses the ranges of a map section, `from` and `to` come from its header.
+    pub fn parse(from: &str, to: &str, section: &Section) -> Result<Self> {
+        let mut numbered = section
+            .numbered()
+            .map(|(number, line)| {
+                extract_mapping(line)
+                    .map(|range| (range, number))
+                    .with_context(|| format!("Failed to parse line {number}"))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        numbered.sort_by_key(|(range, _)| range.source);
+
+        let (ranges, lines) = numbered.into_iter().unzip();
+
+        Ok(Self {
+            from: from.to_string(),
+            to: to.to_string(),
+            ranges,
+            lines,
+        })
+    }
+
+    pub fn name(&self) -> String {
+        format!("{}-to-{}", self.from, self.to)
+    }
+
+    /// Number and text of the line the range at `index` was parsed from.
+    pub fn line(&self, index: usize) -> (usize, String) {
+        let range = &self.ranges[index];
+
+        (
+            self.lines[index],
+            format!("{} {} {}", range.destination, range.source, range.length),
+        )
+    }
+
+    /// Index of the range that maps `value`, `None` if it maps to itself.
+    pub fn matching(&self, value: Id) -> Option<usize> {
+        self.ranges
+            .iter()
+            .position(|range| range.get_destination(value).is_some())
+    }
+}
+
+/// Chain of maps, each continuing with the category the previous one ends in.
+///
+/// Unlike [`crate::Almanac`] it knows nothing about the categories, any
+/// connected chain of maps works.
+#[derive(Debug, PartialEq)]
+pub struct Pipeline {
+    stages: Vec<Stage>,
+}
+
+pub(crate) fn parse_header(line: &str) -> Option<(&str, &str)> {
+    line.strip_suffix(" map:")?.split_once("-to-")
+}
+
+impl Pipeline {
+    /// Chains the stages, each one has to continue from the previous one.
+    pub fn new(stages: Vec<Stage>) -> Result<Self> {
+        if stages.is_empty() {
+            bail!("No maps found");
+        }
+
+        for pair in stages.windows(2) {
+            let (previous, stage) = (&pair[0], &pair[1]);
+
+            if previous.to != stage.from {
+                bail!(
+                    "Map {} does not continue from {}",
+                    stage.name(),
+                    previous.name()
+                );
+            }
+        }
+
+        Ok(Self { stages })
+    }
+
+    /// Parses the map sections, everything after the seed line.
+    pub fn parse(lines: &[&str]) -> Result<Self> {
+        let input = lines.join("\n");
+        let stages = Dispatcher::new()
+            .on(parse_header, |(from, to), section| {
+                Stage::parse(from, to, section)
+            })
+            .parse(&input)?;
+
+        Self::new(stages)
+    }
+
+    pub fn stages(&self) -> &[Stage] {
+        &self.stages
+    }
+
+    /// Every category from the first source to the last destination.
+    pub fn categories(&self) -> Vec<&str> {
+        self.stages
+            .first()
+            .map(|stage| stage.from.as_str())
+            .into_iter()
+            .chain(self.stages.iter().map(|stage| stage.to.as_str()))
+            .collect()
+    }
+
+    /// Value of every category for `value` of the first one.
+    pub fn trace(&self, value: Id) -> Vec<Id> {
+        let mut trace = vec![value];
+        let mut value = value;
+
+        for stage in &self.stages {
+            value = lookup(&stage.ranges, value);
+            trace.push(value);
+        }
+
+        trace
+    }
+
+    /// Maps a value of the first category to the last one.
+    pub fn map(&self, value: Id) -> Id {
+        self.stages
+            .iter()
+            .fold(value, |value, stage| lookup(&stage.ranges, value))
+    }
+
+    pub fn map_intervals(&self, intervals: &IntervalSet<Id>) -> IntervalSet<Id> {
+        self.stages
+            .iter()
+            .fold(intervals.clone(), |intervals, stage| {
+                map_intervals(&stage.ranges, &intervals)
+            })
+    }
+
+    /// All stages as a single map from the first category to the last one.
+    pub fn compose(&self) -> PiecewiseMap {
+        self.stages
+            .iter()
+            .fold(PiecewiseMap::identity(), |composed, stage| {
+                composed.then(&PiecewiseMap::from_ranges(&stage.ranges))
+            })
+    }
+
+    /// Every value of the first category that maps to `value`, in ascending order.
+    pub fn sources(&self, value: Id) -> Vec<Id> {
+        let mut sources = self.stages.iter().rev().fold(vec![value], |values, stage| {
+            values
+                .into_iter()
+                .flat_map(|value| reverse_lookup(&stage.ranges, value))
+                .collect()
+        });
+        sources.sort();
+        sources.dedup();
+
+        sources
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn input() -> Vec<&'static str> {
+        vec![
+            "",
+            "seed-to-soil map:",
+            "50 98 2",
+            "52 50 48",
+            "",
+            "soil-to-dirt map:",
+            "0 15 37",
+            "",
+            "dirt-to-location map:",
+            "100 0 10",
+        ]
+    }
+
+    #[test]
+    fn test_parse() {
+        let pipeline = Pipeline::parse(&input()).unwrap();
+
+        assert_eq!(
+            pipeline.categories(),
+            vec!["seed", "soil", "dirt", "location"]
+        );
+        assert_eq!(
+            pipeline.stages()[0].ranges,
+            vec![Range::new(50, 52, 48), Range::new(98, 50, 2)]
+        );
+        assert_eq!(pipeline.stages()[0].lines, vec![4, 3]);
+        assert_eq!(pipeline.stages()[0].name(), "seed-to-soil");
+        assert_eq!(pipeline.stages()[0].matching(99), Some(1));
+        assert_eq!(pipeline.stages()[0].matching(10), None);
+        assert_eq!(pipeline.stages()[0].line(1), (3, "50 98 2".to_string()));
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let mut broken = input();
+        broken[8] = "water-to-location map:";
+        assert!(Pipeline::parse(&broken).is_err());
+
+        let mut broken = input();
+        broken[3] = "52 x 48";
+        assert!(Pipeline::parse(&broken).is_err());
+
+        assert!(Pipeline::parse(&["", "50 98 2"]).is_err());
+        assert!(Pipeline::parse(&[""]).is_err());
+    }
+
+    #[test]
+    fn test_map_and_trace() {
+        let pipeline = Pipeline::parse(&input()).unwrap();
+
+        // 98 -> 50 -> 35 -> 35, 1 -> 1 -> 1 -> 101
+        assert_eq!(pipeline.trace(98), vec![98, 50, 35, 35]);
+        assert_eq!(pipeline.map(98), 35);
+        assert_eq!(pipeline.map(1), 101);
+        assert_eq!(pipeline.sources(101), vec![1, 16, 101]);
+        assert_eq!(pipeline.sources(35), vec![98]);
+
+        let composed = pipeline.compose();
+        for value in [0, 1, 14, 15, 50, 51, 97, 98, 99, 100, 1000] {
+            assert_eq!(composed.get(value), pipeline.map(value), "value {value}");
+        }
+    }
+}
@@ -0,0 +1,278 @@
+use std::fmt::Write;
+use std::path::PathBuf;
+
+use anyhow::{bail, Result};
+use clap::{Parser, ValueEnum};
+
+use crate::part1::PlantDetails;
+use crate::pipeline::Pipeline;
+use crate::utils::{
+    Fertilizer, Humidity, Id, Light, Location, Seed, Soil, Temperature, Water, CATEGORIES,
+};
+
+/// How one stage mapped a value.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Step {
+    pub map: String,
+    pub source: Id,
+    pub destination: Id,
+    /// Number and text of the matching range line, `None` for the identity.
+    pub line: Option<(usize, String)>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SeedTrace {
+    /// First category of the chain, `seed` unless the maps were renamed.
+    pub category: String,
+    pub seed: Id,
+    /// Every category by name, only for the usual seed to location maps.
+    pub details: Option<PlantDetails>,
+    pub steps: Vec<Step>,
+}
+
+/// The chain as [`PlantDetails`], if the maps are those of an [`crate::Almanac`].
+fn details(pipeline: &Pipeline, values: &[Id]) -> Option<PlantDetails> {
+    (pipeline.categories() == CATEGORIES).then(|| PlantDetails {
+        seed: Seed(values[0]),
+        soil: Soil(values[1]),
+        fertilizer: Fertilizer(values[2]),
+        water: Water(values[3]),
+        light: Light(values[4]),
+        temperature: Temperature(values[5]),
+        humidity: Humidity(values[6]),
+        location: Location(values[7]),
+    })
+}
+
+/// The chain of `seed` together with the range that matched in every stage.
+pub fn trace(pipeline: &Pipeline, seed: Id) -> SeedTrace {
+    let values = pipeline.trace(seed);
+
+    let steps = pipeline
+        .stages()
+        .iter()
+        .zip(values.windows(2))
+        .map(|(stage, values)| Step {
+            map: stage.name(),
+            source: values[0],
+            destination: values[1],
+            line: stage.matching(values[0]).map(|index| stage.line(index)),
+        })
+        .collect();
+
+    SeedTrace {
+        category: pipeline.categories()[0].to_string(),
+        seed,
+        details: details(pipeline, &values),
+        steps,
+    }
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq)]
+pub enum Format {
+    /// One block per seed
+    Table,
+    /// One row per seed and stage
+    Csv,
+}
+
+pub fn render(traces: &[SeedTrace], format: Format) -> String {
+    let mut out = String::new();
+
+    match format {
+        Format::Table => {
+            for trace in traces {
+                let _ = match &trace.details {
+                    Some(details) => writeln!(out, "{details}"),
+                    None => writeln!(out, "{} {}", trace.category, trace.seed),
+                };
+                let _ = writeln!(
+                    out,
+                    "{:<24} {:>12} {:>12}  range",
+                    "map", "source", "destination"
+                );
+
+                for step in &trace.steps {
+                    let range = match &step.line {
+                        Some((number, text)) => format!("line {number}: {text}"),
+                        None => "identity".to_string(),
+                    };
+                    let _ = writeln!(
+                        out,
+                        "{:<24} {:>12} {:>12}  {range}",
+                        step.map, step.source, step.destination
+                    );
+                }
+
+                let _ = writeln!(out);
+            }
+        }
+        Format::Csv => {
+            let _ = writeln!(out, "seed,map,source,destination,line,range");
+
+            for trace in traces {
+                for step in &trace.steps {
+                    let (number, text) = match &step.line {
+                        Some((number, text)) => (number.to_string(), text.as_str()),
+                        None => (String::new(), ""),
+                    };
+                    let _ = writeln!(
+                        out,
+                        "{},{},{},{},{number},{text}",
+                        trace.seed, step.map, step.source, step.destination
+                    );
+                }
+            }
+        }
+    }
+
+    out
+}
+
+/// Show how seeds are mapped to their location, stage by stage
+#[derive(Parser, Debug)]
+pub struct Command {
+    /// Seed to trace, can be repeated
+    #[arg(long, required_unless_present = "seeds_from_input")]
+    seed: Vec<Id>,
+
+    /// Trace every seed listed in the input
+    #[arg(long)]
+    seeds_from_input: bool,
+
+    /// Read the almanac from this file instead of the puzzle input
+    #[arg(short, long)]
+    input: Option<PathBuf>,
+
+    /// Print a table or CSV
+    #[arg(long, value_enum, default_value_t = Format::Table)]
+    format: Format,
+}
+
+impl common::CommandRunner for Command {
+    fn run(&self) -> Result<()> {
+        let input = crate::read_input(self.input.as_deref())?;
+
+        let mut seeds = self.seed.clone();
+        if self.seeds_from_input {
+            seeds.extend(input.seeds.iter().map(|seed| seed.0));
+        }
+        if seeds.is_empty() {
+            bail!("No seeds to trace");
+        }
+
+        let traces: Vec<_> = seeds
+            .into_iter()
+            .map(|seed| trace(&input.pipeline, seed))
+            .collect();
+
+        print!("{}", render(&traces, self.format));
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn input() -> crate::Input {
+        crate::parse(
+            "seeds: 79 14 55 13
+
+             seed-to-soil map:
+             50 98 2
+             52 50 48
+
+             soil-to-fertilizer map:
+             0 15 37
+             37 52 2
+             39 0 15
+
+             fertilizer-to-water map:
+             49 53 8
+             0 11 42
+             42 0 7
+             57 7 4
+
+             water-to-light map:
+             88 18 7
+             18 25 70
+
+             light-to-temperature map:
+             45 77 23
+             81 45 19
+             68 64 13
+
+             temperature-to-humidity map:
+             0 69 1
+             1 0 69
+
+             humidity-to-location map:
+             60 56 37
+             56 93 4",
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_trace() {
+        let input = input();
+        let trace = trace(&input.pipeline, 79);
+
+        assert_eq!(trace.details.unwrap().location, Location(82));
+        assert_eq!(
+            trace
+                .steps
+                .iter()
+                .map(|step| step.destination)
+                .collect::<Vec<_>>(),
+            vec![81, 81, 81, 74, 78, 78, 82]
+        );
+        assert_eq!(trace.steps[0].line, Some((5, "52 50 48".to_string())));
+        assert_eq!(trace.steps[1].line, None);
+    }
+
+    #[test]
+    fn test_render() {
+        let input = input();
+        let traces = vec![trace(&input.pipeline, 79)];
+
+        let csv = render(&traces, Format::Csv);
+        let lines: Vec<_> = csv.lines().collect();
+        assert_eq!(lines.len(), 8);
+        assert_eq!(lines[1], "79,seed-to-soil,79,81,5,52 50 48");
+        assert_eq!(lines[2], "79,soil-to-fertilizer,81,81,,");
+
+        let table = render(&traces, Format::Table);
+        assert!(table.starts_with("seed 79, soil 81,"));
+        assert!(table.contains("identity"));
+    }
+
+    #[test]
+    fn test_other_categories() {
+        let input = crate::parse(
+            "seeds: 79 14
+
+             plant-to-dirt map:
+             50 98 2
+             52 50 48
+
+             dirt-to-location map:
+             0 80 10",
+        )
+        .unwrap();
+        assert!(input.almanac().is_err());
+
+        let traces = vec![trace(&input.pipeline, 79)];
+        assert_eq!(traces[0].details, None);
+
+        let table = render(&traces, Format::Table);
+        assert!(table.starts_with("plant 79\n"));
+        assert!(table.contains("plant-to-dirt"));
+        assert!(table.contains("line 8: 0 80 10"));
+
+        let csv = render(&traces, Format::Csv);
+        assert!(csv.contains("79,dirt-to-location,81,1,8,0 80 10"));
+    }
+}
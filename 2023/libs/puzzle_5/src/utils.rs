@@ -1,19 +1,14 @@
 use anyhow::{bail, Context, Result};
+use common::interval::{Interval, IntervalSet};
+use common::parse;
 use derive_more::Display;
+use nom::bytes::complete::tag;
 use std::fmt::Debug;
 
-pub type Id = u64;
+use crate::pipeline::{Pipeline, Stage};
+use crate::validate::{check, Validation};
 
-#[derive(Debug, Display)]
-enum BlockType {
-    SeedToSoil,
-    SoilToFertilizer,
-    FertilizerToWater,
-    WaterToLight,
-    LightToTemperature,
-    TemperatureToHumidity,
-    HumidityToLocation,
-}
+pub type Id = u64;
 
 #[derive(Debug, Display, Clone, Copy, Hash, Eq, PartialEq, Ord, PartialOrd)]
 pub struct Seed(pub Id);
@@ -160,11 +155,17 @@ impl From<Humidity> for Location {
         Location(humidity.0)
     }
 }
+/// A line of a map, `length` values from `source` on map to `destination` on.
+///
+/// Values a range cannot map below [`Id::MAX`] are not covered by it, they fall
+/// through to the next range or the identity like any other unmapped value.
+/// Lookups, intervals and composition all go by the covered part.
 #[derive(PartialEq)]
 pub struct Range<S: Into<Id>, D: From<Id> + From<S>> {
     pub source: Id,
     pub destination: Id,
     pub length: Id,
+    covered: Id,
     phantom: std::marker::PhantomData<(S, D)>,
 }
 
@@ -191,20 +192,121 @@ impl<S: Into<Id> + Copy, D: From<Id> + From<S>> Range<S, D> {
             source,
             destination,
             length,
+            covered: length.min(Id::MAX - source).min(Id::MAX - destination),
             phantom: std::marker::PhantomData,
         }
     }
+
+    /// Whether the range maps `source`, see [`Range`] for ranges that overflow.
     pub fn contains(&self, source: S) -> bool {
-        source.into() >= self.source && source.into() < self.source + self.length
+        let source: Id = source.into();
+
+        source >= self.source && source - self.source < self.covered
     }
 
+    /// `None` where the range does not map `source`.
     pub fn get_destination(&self, source: S) -> Option<D> {
-        if self.contains(source) {
-            Some((source.into() - self.source + self.destination).into())
-        } else {
-            None
+        self.contains(source)
+            .then(|| D::from(self.destination + (source.into() - self.source)))
+    }
+
+    pub fn source_interval(&self) -> Interval<Id> {
+        Interval::new(self.source, self.source + self.covered)
+    }
+
+    pub fn destination_interval(&self) -> Interval<Id> {
+        Interval::new(self.destination, self.destination + self.covered)
+    }
+
+    /// Destination of a source interval that lies within this range.
+    fn shift(&self, interval: Interval<Id>) -> Interval<Id> {
+        Interval::new(
+            self.destination + (interval.start - self.source),
+            self.destination + (interval.end - self.source),
+        )
+    }
+}
+
+impl<S: Into<Id> + From<Id> + Copy, D: From<Id> + From<S> + Into<Id> + Copy> Range<S, D> {
+    /// Source mapped to `destination` by this range, if the range covers it.
+    pub fn get_source(&self, destination: D) -> Option<S> {
+        let destination: Id = destination.into();
+
+        (destination >= self.destination && destination - self.destination < self.covered)
+            .then(|| S::from(self.source + (destination - self.destination)))
+    }
+}
+
+pub(crate) fn lookup<S: Into<Id> + Copy, D: From<Id> + From<S>>(
+    ranges: &[Range<S, D>],
+    source: S,
+) -> D {
+    ranges
+        .iter()
+        .filter_map(|range| range.get_destination(source))
+        .next()
+        .unwrap_or_else(|| D::from(source))
+}
+
+/// Every source that [`lookup`] maps to `destination`, in ascending order.
+///
+/// Ranges may overlap each other and the identity of the gaps, so there can be
+/// none or several. Candidates are checked with a forward lookup.
+pub(crate) fn reverse_lookup<S, D>(ranges: &[Range<S, D>], destination: D) -> Vec<S>
+where
+    S: Into<Id> + From<Id> + Copy + Ord,
+    D: From<Id> + From<S> + Into<Id> + Copy + PartialEq,
+{
+    let identity = S::from(destination.into());
+
+    let mut sources: Vec<S> = ranges
+        .iter()
+        .filter_map(|range| range.get_source(destination))
+        .chain(std::iter::once(identity))
+        .filter(|source| lookup(ranges, *source) == destination)
+        .collect();
+    sources.sort();
+    sources.dedup();
+
+    sources
+}
+
+/// Maps whole intervals through `ranges`, which have to be sorted by source.
+///
+/// Every interval is split at the range boundaries, parts inside a range are
+/// shifted to its destination and the gaps in between map to themselves.
+pub fn map_intervals<S: Into<Id> + Copy, D: From<Id> + From<S>>(
+    ranges: &[Range<S, D>],
+    intervals: &IntervalSet<Id>,
+) -> IntervalSet<Id> {
+    let mut mapped = Vec::new();
+
+    for interval in intervals.iter() {
+        let mut rest = Some(*interval);
+
+        for range in ranges {
+            let Some(current) = rest else {
+                break;
+            };
+            let source = range.source_interval();
+
+            let (gap, from_range) = current.split_at(source.start);
+            mapped.extend(gap);
+
+            rest = match from_range {
+                Some(from_range) => {
+                    let (inside, above) = from_range.split_at(source.end);
+                    mapped.extend(inside.map(|inside| range.shift(inside)));
+                    above
+                }
+                None => None,
+            };
         }
+
+        mapped.extend(rest);
     }
+
+    mapped.into_iter().collect()
 }
 
 #[derive(Debug, PartialEq)]
@@ -220,155 +322,149 @@ pub struct Almanac {
 
 impl Almanac {
     pub fn soil(&self, seed: Seed) -> Soil {
-        self.seed_to_soil
-            .iter()
-            .filter_map(|range| range.get_destination(seed))
-            .next()
-            .unwrap_or_else(|| seed.into())
+        lookup(&self.seed_to_soil, seed)
     }
     pub fn fertilizer(&self, soil: Soil) -> Fertilizer {
-        self.soil_to_fertilizer
-            .iter()
-            .filter_map(|range| range.get_destination(soil))
-            .next()
-            .unwrap_or_else(|| soil.into())
+        lookup(&self.soil_to_fertilizer, soil)
     }
     pub fn water(&self, fertilizer: Fertilizer) -> Water {
-        self.fertilizer_to_water
-            .iter()
-            .filter_map(|range| range.get_destination(fertilizer))
-            .next()
-            .unwrap_or_else(|| fertilizer.into())
+        lookup(&self.fertilizer_to_water, fertilizer)
     }
     pub fn light(&self, water: Water) -> Light {
-        self.water_to_light
-            .iter()
-            .filter_map(|range| range.get_destination(water))
-            .next()
-            .unwrap_or_else(|| water.into())
+        lookup(&self.water_to_light, water)
     }
     pub fn temperature(&self, light: Light) -> Temperature {
-        self.light_to_temperature
-            .iter()
-            .filter_map(|range| range.get_destination(light))
-            .next()
-            .unwrap_or_else(|| light.into())
+        lookup(&self.light_to_temperature, light)
     }
     pub fn humidity(&self, temperature: Temperature) -> Humidity {
-        self.temperature_to_humidity
-            .iter()
-            .filter_map(|range| range.get_destination(temperature))
-            .next()
-            .unwrap_or_else(|| temperature.into())
+        lookup(&self.temperature_to_humidity, temperature)
     }
     pub fn location(&self, humidity: Humidity) -> Location {
-        self.humidity_to_location
-            .iter()
-            .filter_map(|range| range.get_destination(humidity))
-            .next()
-            .unwrap_or_else(|| humidity.into())
+        lookup(&self.humidity_to_location, humidity)
+    }
+
+    pub fn seeds(&self, soil: Soil) -> Vec<Seed> {
+        reverse_lookup(&self.seed_to_soil, soil)
+    }
+    pub fn soils(&self, fertilizer: Fertilizer) -> Vec<Soil> {
+        reverse_lookup(&self.soil_to_fertilizer, fertilizer)
+    }
+    pub fn fertilizers(&self, water: Water) -> Vec<Fertilizer> {
+        reverse_lookup(&self.fertilizer_to_water, water)
+    }
+    pub fn waters(&self, light: Light) -> Vec<Water> {
+        reverse_lookup(&self.water_to_light, light)
+    }
+    pub fn lights(&self, temperature: Temperature) -> Vec<Light> {
+        reverse_lookup(&self.light_to_temperature, temperature)
+    }
+    pub fn temperatures(&self, humidity: Humidity) -> Vec<Temperature> {
+        reverse_lookup(&self.temperature_to_humidity, humidity)
+    }
+    pub fn humidities(&self, location: Location) -> Vec<Humidity> {
+        reverse_lookup(&self.humidity_to_location, location)
+    }
+
+    /// Every seed that ends up at `location`, in ascending order.
+    pub fn seeds_at(&self, location: Location) -> Vec<Seed> {
+        let mut seeds: Vec<Seed> = self
+            .humidities(location)
+            .into_iter()
+            .flat_map(|humidity| self.temperatures(humidity))
+            .flat_map(|temperature| self.lights(temperature))
+            .flat_map(|light| self.waters(light))
+            .flat_map(|water| self.fertilizers(water))
+            .flat_map(|fertilizer| self.soils(fertilizer))
+            .flat_map(|soil| self.seeds(soil))
+            .collect();
+        seeds.sort();
+        seeds.dedup();
+
+        seeds
     }
 }
 
-fn extract_mapping<S: Into<Id> + Copy, D: From<Id> + From<S>>(line: &str) -> Result<Range<S, D>> {
+/// Numbers of the `seeds: 79 14 55 13` line.
+pub(crate) fn extract_seed_numbers(line: &str) -> Result<Vec<Id>> {
+    let (_, numbers) = parse::finish(
+        parse::key_value(tag("seeds"), parse::unsigned_list),
+        line.trim(),
+    )?;
+
+    Ok(numbers)
+}
+
+pub(crate) fn extract_mapping<S: Into<Id> + Copy, D: From<Id> + From<S>>(
+    line: &str,
+) -> Result<Range<S, D>> {
     let trimmed = line.trim();
 
     if trimmed.is_empty() {
         bail!("Empty line");
     }
 
-    let parts: Result<Vec<Id>> = trimmed
-        .split(' ')
-        .map(|num| num.trim())
-        .filter(|num| !num.is_empty())
-        .map(|num| {
-            num.parse()
-                .with_context(|| format!("Failed to parse {num}"))
-        })
-        .collect();
+    let parts = parse::finish(parse::unsigned_list, trimmed)
+        .with_context(|| format!("Failed to parse {line}"))?;
 
-    let parts = parts.with_context(|| format!("Failed to parse {line}"))?;
-
-    if parts.len() != 3 {
+    let [destination, source, length] = parts[..] else {
         bail!("Invalid line: {}", line);
-    }
-
-    let destination = parts[0];
-    let source = parts[1];
-    let length = parts[2];
+    };
 
     Ok(Range::<S, D>::new(source, destination, length))
 }
 
-pub fn parse_input(input: &[&str]) -> Result<Almanac> {
-    let mut seed_to_soil = Vec::new();
-    let mut soil_to_fertilizer = Vec::new();
-    let mut fertilizer_to_water = Vec::new();
-    let mut water_to_light = Vec::new();
-    let mut light_to_temperature = Vec::new();
-    let mut temperature_to_humidity = Vec::new();
-    let mut humidity_to_location = Vec::new();
-
-    let mut block: Option<BlockType> = None;
-    for line in input {
-        let line = line.trim();
-
-        if block.is_some() {
-            if line.is_empty() {
-                block = None;
-            } else if let Some(block_type) = &block {
-                match block_type {
-                    BlockType::SeedToSoil => seed_to_soil.push(extract_mapping(line)?),
-                    BlockType::SoilToFertilizer => soil_to_fertilizer.push(extract_mapping(line)?),
-                    BlockType::FertilizerToWater => {
-                        fertilizer_to_water.push(extract_mapping(line)?)
-                    }
-                    BlockType::WaterToLight => water_to_light.push(extract_mapping(line)?),
-                    BlockType::LightToTemperature => {
-                        light_to_temperature.push(extract_mapping(line)?)
-                    }
-                    BlockType::TemperatureToHumidity => {
-                        temperature_to_humidity.push(extract_mapping(line)?)
-                    }
-                    BlockType::HumidityToLocation => {
-                        humidity_to_location.push(extract_mapping(line)?)
-                    }
-                }
-            }
-        } else if line.contains("seed-to-soil map") {
-            block = Some(BlockType::SeedToSoil);
-        } else if line.contains("soil-to-fertilizer map") {
-            block = Some(BlockType::SoilToFertilizer);
-        } else if line.contains("fertilizer-to-water map") {
-            block = Some(BlockType::FertilizerToWater);
-        } else if line.contains("water-to-light map") {
-            block = Some(BlockType::WaterToLight);
-        } else if line.contains("light-to-temperature map") {
-            block = Some(BlockType::LightToTemperature);
-        } else if line.contains("temperature-to-humidity map") {
-            block = Some(BlockType::TemperatureToHumidity);
-        } else if line.contains("humidity-to-location map") {
-            block = Some(BlockType::HumidityToLocation);
+/// Categories of the stages of an [`Almanac`], in order.
+pub const CATEGORIES: [&str; 8] = [
+    "seed",
+    "soil",
+    "fertilizer",
+    "water",
+    "light",
+    "temperature",
+    "humidity",
+    "location",
+];
+
+fn typed<S: Into<Id> + Copy, D: From<Id> + From<S>>(stage: &Stage) -> Vec<Range<S, D>> {
+    stage
+        .ranges
+        .iter()
+        .map(|range| Range::new(range.source, range.destination, range.length))
+        .collect()
+}
+
+impl TryFrom<&Pipeline> for Almanac {
+    type Error = anyhow::Error;
+
+    fn try_from(pipeline: &Pipeline) -> Result<Self> {
+        if pipeline.categories() != CATEGORIES {
+            bail!(
+                "Expected the maps {}, got {}",
+                CATEGORIES.join(" -> "),
+                pipeline.categories().join(" -> ")
+            );
         }
+
+        let stages = pipeline.stages();
+
+        Ok(Almanac {
+            seed_to_soil: typed(&stages[0]),
+            soil_to_fertilizer: typed(&stages[1]),
+            fertilizer_to_water: typed(&stages[2]),
+            water_to_light: typed(&stages[3]),
+            light_to_temperature: typed(&stages[4]),
+            temperature_to_humidity: typed(&stages[5]),
+            humidity_to_location: typed(&stages[6]),
+        })
     }
+}
 
-    seed_to_soil.sort_by_key(|range| range.source);
-    soil_to_fertilizer.sort_by_key(|range| range.source);
-    fertilizer_to_water.sort_by_key(|range| range.source);
-    water_to_light.sort_by_key(|range| range.source);
-    light_to_temperature.sort_by_key(|range| range.source);
-    temperature_to_humidity.sort_by_key(|range| range.source);
-    humidity_to_location.sort_by_key(|range| range.source);
+/// Parses and validates the maps, warning about any [`crate::validate::Issue`].
+pub fn parse_input(input: &[&str]) -> Result<Almanac> {
+    let pipeline = Pipeline::parse(input)?;
+    check(&pipeline, Validation::Lenient)?;
 
-    Ok(Almanac {
-        seed_to_soil,
-        soil_to_fertilizer,
-        fertilizer_to_water,
-        water_to_light,
-        light_to_temperature,
-        temperature_to_humidity,
-        humidity_to_location,
-    })
+    Almanac::try_from(&pipeline)
 }
 
 #[cfg(test)]
@@ -428,6 +524,17 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_extract_errors() {
+        assert!(extract_mapping::<Seed, Soil>("50 x 2").is_err());
+        assert!(extract_mapping::<Seed, Soil>("50 98").is_err());
+        assert_eq!(
+            extract_seed_numbers(" seeds:  79 14 ").unwrap(),
+            vec![79, 14]
+        );
+        assert!(extract_seed_numbers("seeds: 79 -14").is_err());
+    }
+
     #[test]
     fn test_parse_input() {
         let input = input();
@@ -521,6 +628,83 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_get_source() {
+        let range = Range::<Seed, Soil>::new(98, 50, 2);
+
+        assert_eq!(range.get_source(Soil(50)), Some(Seed(98)));
+        assert_eq!(range.get_source(Soil(51)), Some(Seed(99)));
+        assert_eq!(range.get_source(Soil(52)), None);
+        assert_eq!(range.get_source(Soil(98)), None);
+    }
+
+    #[test]
+    fn test_reverse_lookup() {
+        let ranges = vec![
+            Range::<Seed, Soil>::new(50, 52, 48),
+            Range::<Seed, Soil>::new(98, 50, 2),
+        ];
+
+        // 51 only comes from 99, seed 51 itself maps to 53
+        assert_eq!(reverse_lookup(&ranges, Soil(51)), vec![Seed(99)]);
+        // 53 comes from seed 51
+        assert_eq!(reverse_lookup(&ranges, Soil(53)), vec![Seed(51)]);
+        // 10 is unmapped
+        assert_eq!(reverse_lookup(&ranges, Soil(10)), vec![Seed(10)]);
+        // 99 is in the destination of 50..98, seed 99 itself maps to 51
+        assert_eq!(reverse_lookup(&ranges, Soil(99)), vec![Seed(97)]);
+        // 98 comes from 96, seed 98 itself maps to 50
+        assert_eq!(reverse_lookup(&ranges, Soil(98)), vec![Seed(96)]);
+    }
+
+    #[test]
+    fn test_seeds_at() {
+        let input = input();
+        let lines: Vec<_> = input.lines().skip(1).collect();
+        let almanac = parse_input(&lines).unwrap();
+
+        assert_eq!(almanac.seeds_at(Location(35)), vec![Seed(13)]);
+        assert_eq!(almanac.seeds_at(Location(46)), vec![Seed(82)]);
+
+        for seed in [0, 14, 55, 79, 98, 99].map(Seed) {
+            let soil = almanac.soil(seed);
+            let fertilizer = almanac.fertilizer(soil);
+            let water = almanac.water(fertilizer);
+            let light = almanac.light(water);
+            let temperature = almanac.temperature(light);
+            let humidity = almanac.humidity(temperature);
+            let location = almanac.location(humidity);
+
+            assert!(almanac.seeds_at(location).contains(&seed));
+        }
+    }
+
+    #[test]
+    fn test_map_intervals() {
+        let ranges = vec![
+            Range::<Seed, Soil>::new(50, 52, 48),
+            Range::<Seed, Soil>::new(98, 50, 2),
+        ];
+        let seeds: IntervalSet<Id> = [Interval::new(40, 60), Interval::new(97, 110)]
+            .into_iter()
+            .collect();
+
+        let soils = map_intervals(&ranges, &seeds);
+
+        // 40..50 gap, 50..60 -> 52..62, 97 -> 99, 98..100 -> 50..52, 100..110 gap
+        let expected: IntervalSet<Id> = [
+            Interval::new(40, 50),
+            Interval::new(52, 62),
+            Interval::new(99, 100),
+            Interval::new(50, 52),
+            Interval::new(100, 110),
+        ]
+        .into_iter()
+        .collect();
+        assert_eq!(soils, expected);
+        assert_eq!(soils.len(), seeds.len());
+    }
+
     #[test]
     fn test_getter() {
         let almanac = Almanac {
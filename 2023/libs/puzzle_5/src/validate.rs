@@ -0,0 +1,231 @@
+use std::fmt::{self, Display};
+
+use anyhow::{bail, Result};
+use common::interval::Interval;
+use log::warn;
+
+use crate::pipeline::{Pipeline, Stage};
+use crate::utils::{Id, Range};
+
+/// How [`check`] treats problems with the maps.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Validation {
+    /// Rejects overlapping, empty and overflowing ranges.
+    Strict,
+    /// Only warns, lookups take the first matching range by source.
+    #[default]
+    Lenient,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum IssueKind {
+    /// A range with length 0 never matches.
+    ZeroLength,
+    /// Source or destination end past [`Id::MAX`], the range only covers what
+    /// it can map below that.
+    Overflow,
+    /// Source values covered by more than one range.
+    Overlap(Interval<Id>),
+    /// Source values between two ranges, they map to themselves.
+    Gap(Interval<Id>),
+}
+
+/// Problem with the ranges of one map, with the lines they come from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Issue {
+    pub map: String,
+    pub kind: IssueKind,
+    /// Number and text of every offending line.
+    pub lines: Vec<(usize, String)>,
+}
+
+impl Issue {
+    /// Gaps are fine, the identity covers them, everything else is an error.
+    pub fn is_error(&self) -> bool {
+        !matches!(self.kind, IssueKind::Gap(_))
+    }
+}
+
+impl Display for Issue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.kind {
+            IssueKind::ZeroLength => write!(f, "{} map: zero length range", self.map)?,
+            IssueKind::Overflow => write!(f, "{} map: range overflows", self.map)?,
+            IssueKind::Overlap(overlap) => write!(f, "{} map: overlap {overlap}", self.map)?,
+            IssueKind::Gap(gap) => write!(f, "{} map: gap {gap}", self.map)?,
+        }
+
+        let lines: Vec<_> = self
+            .lines
+            .iter()
+            .map(|(number, text)| format!("line {number} `{text}`"))
+            .collect();
+
+        write!(f, " in {}", lines.join(" and "))
+    }
+}
+
+fn overflows(range: &Range<Id, Id>) -> bool {
+    range.source.checked_add(range.length).is_none()
+        || range.destination.checked_add(range.length).is_none()
+}
+
+fn validate_stage(stage: &Stage) -> Vec<Issue> {
+    let issue = |kind, indices: &[usize]| Issue {
+        map: stage.name(),
+        kind,
+        lines: indices.iter().map(|index| stage.line(*index)).collect(),
+    };
+    let mut issues = Vec::new();
+
+    for (index, range) in stage.ranges.iter().enumerate() {
+        if range.length == 0 {
+            issues.push(issue(IssueKind::ZeroLength, &[index]));
+        }
+        if overflows(range) {
+            issues.push(issue(IssueKind::Overflow, &[index]));
+        }
+    }
+
+    // Ranges are sorted by source, so each one can only overlap the ones after
+    // it that start before its end.
+    let non_empty: Vec<usize> = (0..stage.ranges.len())
+        .filter(|index| stage.ranges[*index].length > 0)
+        .collect();
+
+    for (position, first) in non_empty.iter().enumerate() {
+        let source = stage.ranges[*first].source_interval();
+
+        for second in &non_empty[position + 1..] {
+            match source.intersect(&stage.ranges[*second].source_interval()) {
+                Some(overlap) => {
+                    issues.push(issue(IssueKind::Overlap(overlap), &[*first, *second]))
+                }
+                None => break,
+            }
+        }
+    }
+
+    // The end covered so far and the range it comes from.
+    let mut covered: Option<(Id, usize)> = None;
+    for index in non_empty {
+        let source = stage.ranges[index].source_interval();
+
+        if let Some((end, previous)) = covered {
+            if end < source.start {
+                let gap = Interval::new(end, source.start);
+                issues.push(issue(IssueKind::Gap(gap), &[previous, index]));
+            }
+        }
+        if covered.is_none_or(|(end, _)| source.end > end) {
+            covered = Some((source.end, index));
+        }
+    }
+
+    issues
+}
+
+/// Every zero length, overflowing or overlapping range and every gap between
+/// ranges, map by map.
+pub fn validate(pipeline: &Pipeline) -> Vec<Issue> {
+    pipeline.stages().iter().flat_map(validate_stage).collect()
+}
+
+/// Warns about every issue, and fails on errors in [`Validation::Strict`] mode.
+pub fn check(pipeline: &Pipeline, validation: Validation) -> Result<()> {
+    let issues = validate(pipeline);
+    let errors: Vec<_> = issues.iter().filter(|issue| issue.is_error()).collect();
+
+    if validation == Validation::Strict && !errors.is_empty() {
+        let errors: Vec<_> = errors.iter().map(|issue| issue.to_string()).collect();
+        bail!("Invalid almanac:\n{}", errors.join("\n"));
+    }
+
+    for issue in &issues {
+        warn!("{issue}");
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pipeline(lines: &[&str]) -> Pipeline {
+        let lines: Vec<_> = ["", "seed-to-soil map:"]
+            .iter()
+            .chain(lines)
+            .copied()
+            .collect();
+
+        Pipeline::parse(&lines).unwrap()
+    }
+
+    #[test]
+    fn test_valid() {
+        let pipeline = pipeline(&["50 98 2", "52 50 48"]);
+
+        assert!(validate(&pipeline).is_empty());
+        assert!(check(&pipeline, Validation::Strict).is_ok());
+    }
+
+    #[test]
+    fn test_issues() {
+        let pipeline = pipeline(&[
+            "0 10 5",
+            "100 12 5",
+            "7 30 0",
+            "0 40 2",
+            "0 18446744073709551610 10",
+        ]);
+
+        assert_eq!(
+            validate(&pipeline),
+            vec![
+                Issue {
+                    map: "seed-to-soil".to_string(),
+                    kind: IssueKind::ZeroLength,
+                    lines: vec![(5, "7 30 0".to_string())],
+                },
+                Issue {
+                    map: "seed-to-soil".to_string(),
+                    kind: IssueKind::Overflow,
+                    lines: vec![(7, "0 18446744073709551610 10".to_string())],
+                },
+                Issue {
+                    map: "seed-to-soil".to_string(),
+                    kind: IssueKind::Overlap(Interval::new(12, 15)),
+                    lines: vec![(3, "0 10 5".to_string()), (4, "100 12 5".to_string())],
+                },
+                Issue {
+                    map: "seed-to-soil".to_string(),
+                    kind: IssueKind::Gap(Interval::new(17, 40)),
+                    lines: vec![(4, "100 12 5".to_string()), (6, "0 40 2".to_string())],
+                },
+                Issue {
+                    map: "seed-to-soil".to_string(),
+                    kind: IssueKind::Gap(Interval::new(42, 18446744073709551610)),
+                    lines: vec![
+                        (6, "0 40 2".to_string()),
+                        (7, "0 18446744073709551610 10".to_string())
+                    ],
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_check() {
+        let overlapping = pipeline(&["0 10 5", "100 12 5"]);
+        let error = check(&overlapping, Validation::Strict).unwrap_err();
+
+        assert!(error
+            .to_string()
+            .contains("line 3 `0 10 5` and line 4 `100 12 5`"));
+        assert!(check(&overlapping, Validation::Lenient).is_ok());
+
+        let gap = pipeline(&["0 10 5", "100 20 5"]);
+        assert!(check(&gap, Validation::Strict).is_ok());
+    }
+}
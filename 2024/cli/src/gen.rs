@@ -0,0 +1,52 @@
+use std::fs;
+use std::path::PathBuf;
+
+use anyhow::{Context, Result};
+use clap::Parser;
+use log::info;
+
+use crate::registry;
+
+/// Generate a random input for a puzzle
+#[derive(Parser, Debug)]
+pub struct Command {
+    /// Day of the puzzle
+    #[arg(short, long)]
+    day: u8,
+
+    /// Rough number of entries (lines, rows, instructions, ...) to generate
+    #[arg(short = 'n', long, default_value_t = 1000)]
+    size: usize,
+
+    /// Seed, the same seed always gives the same input
+    #[arg(short, long, default_value_t = 0)]
+    seed: u64,
+
+    /// Write the input to this file instead of stdout
+    #[arg(short, long)]
+    output: Option<PathBuf>,
+}
+
+impl common::CommandRunner for Command {
+    fn run(&self) -> Result<()> {
+        let entry = registry::find(self.day)?;
+
+        let input = entry.generate(self.size, self.seed);
+
+        match &self.output {
+            Some(output) => {
+                if let Some(parent) = output.parent() {
+                    fs::create_dir_all(parent)
+                        .with_context(|| format!("Failed to create {}", parent.display()))?;
+                }
+                fs::write(output, input + "\n")
+                    .with_context(|| format!("Failed to write {}", output.display()))?;
+
+                info!("Wrote input for day {} to {}", self.day, output.display());
+            }
+            None => println!("{input}"),
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,344 @@
+use std::fs;
+use std::panic;
+use std::path::PathBuf;
+
+use anyhow::{bail, Context, Result};
+use clap::Parser;
+use common::puzzle::{disagreements, input_file};
+use common::Entry;
+use log::{debug, info};
+
+use crate::registry;
+
+/// Shrink an input to a minimal reproducer of a crash or a disagreement between variants
+#[derive(Parser, Debug)]
+pub struct Command {
+    /// Day of the puzzle
+    #[arg(short, long)]
+    day: u8,
+
+    /// Read the input from this file instead of the puzzle input
+    #[arg(short, long)]
+    input: Option<PathBuf>,
+
+    /// Write the reproducer to this file instead of a new fixture of the puzzle crate
+    #[arg(short, long)]
+    output: Option<PathBuf>,
+}
+
+impl common::CommandRunner for Command {
+    fn run(&self) -> Result<()> {
+        let entry = registry::find(self.day)?;
+
+        let input_file = match &self.input {
+            Some(input) => input.clone(),
+            None => PathBuf::from(input_file(self.day)),
+        };
+        let input = fs::read_to_string(&input_file)
+            .with_context(|| format!("Failed to read input file {}", input_file.display()))?;
+
+        // Every attempt may panic, keep the output readable.
+        let hook = panic::take_hook();
+        panic::set_hook(Box::new(|_| {}));
+        let minimized = minimize(&entry, &input);
+        panic::set_hook(hook);
+
+        let Some((failure, minimized)) = minimized else {
+            bail!(
+                "Day {} does not fail on {}, nothing to minimize",
+                self.day,
+                input_file.display()
+            );
+        };
+
+        let output = match &self.output {
+            Some(output) => output.clone(),
+            None => next_fixture(self.day),
+        };
+        if let Some(parent) = output.parent() {
+            fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create {}", parent.display()))?;
+        }
+        fs::write(&output, format!("{minimized}\n"))
+            .with_context(|| format!("Failed to write {}", output.display()))?;
+
+        info!(
+            "Minimized {} to {} lines ({failure:?})",
+            input_file.display(),
+            minimized.lines().count()
+        );
+        println!("{minimized}");
+        println!("Wrote reproducer to {}", output.display());
+
+        Ok(())
+    }
+}
+
+/// First unused `libs/puzzle_{day}/fixtures/minimized_{n}.txt`, the `test_fixtures`
+/// test of the puzzle crate cross checks every file in there.
+fn next_fixture(day: u8) -> PathBuf {
+    (1..)
+        .map(|n| PathBuf::from(format!("libs/puzzle_{day}/fixtures/minimized_{n}.txt")))
+        .find(|path| !path.exists())
+        .unwrap()
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Crash {
+    Error(String),
+    Panic(String),
+}
+
+impl Crash {
+    /// Numbers are left out of the messages, they tend to change while shrinking
+    /// (`the len is 3 but the index is 5`, `invalid digit in line 40`).
+    fn from_message(message: &str) -> Crash {
+        let without_digits = |text: &str| text.chars().filter(|c| !c.is_ascii_digit()).collect();
+
+        match message.strip_prefix("Panicked: ") {
+            Some(panic) => Crash::Panic(without_digits(panic)),
+            None => Crash::Error(without_digits(message)),
+        }
+    }
+}
+
+/// How an input fails, a smaller input has to fail the same way to be kept.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Failure {
+    Parse(Crash),
+    Part {
+        part: u8,
+        variant: &'static str,
+        crash: Crash,
+    },
+    Mismatch {
+        part: u8,
+    },
+}
+
+fn failure(entry: &Entry, input: &str) -> Option<Failure> {
+    let checks = match entry.crosscheck(input) {
+        Ok(checks) => checks,
+        Err(error) => {
+            return Some(Failure::Parse(Crash::from_message(
+                &error.root_cause().to_string(),
+            )))
+        }
+    };
+
+    if let Some(check) = checks.iter().find(|check| check.outcome.is_err()) {
+        let message = check.outcome.as_ref().unwrap_err();
+
+        return Some(Failure::Part {
+            part: check.part,
+            variant: check.variant,
+            crash: Crash::from_message(message),
+        });
+    }
+
+    disagreements(&checks)
+        .first()
+        .map(|part| Failure::Mismatch { part: *part })
+}
+
+/// Shrinks the input line by line, then token by token, until neither helps any more.
+fn minimize(entry: &Entry, input: &str) -> Option<(Failure, String)> {
+    let expected = failure(entry, input)?;
+    let mut fails =
+        |lines: &[String]| failure(entry, &lines.join("\n")).as_ref() == Some(&expected);
+
+    let mut lines: Vec<String> = input.lines().map(str::to_string).collect();
+
+    loop {
+        let before = lines.join("\n");
+
+        lines = ddmin(lines, &mut fails);
+        debug!("Reduced to {} lines", lines.len());
+
+        for index in 0..lines.len() {
+            let tokens = ddmin(tokens(&lines[index]), &mut |tokens: &[String]| {
+                let mut candidate = lines.clone();
+                candidate[index] = tokens.concat();
+                fails(&candidate)
+            });
+            lines[index] = tokens.concat();
+        }
+
+        if lines.join("\n") == before {
+            break;
+        }
+    }
+
+    Some((expected, lines.join("\n")))
+}
+
+/// Splits a line into words, each keeping the whitespace in front of it.
+fn tokens(line: &str) -> Vec<String> {
+    let mut tokens: Vec<String> = Vec::new();
+    let mut previous_whitespace = true;
+
+    for c in line.chars() {
+        match tokens.last_mut() {
+            Some(token) if !(c.is_whitespace() && !previous_whitespace) => token.push(c),
+            _ => tokens.push(c.to_string()),
+        }
+        previous_whitespace = c.is_whitespace();
+    }
+
+    tokens
+}
+
+/// Delta debugging: removes ever smaller chunks of `items` as long as the input still `fails`.
+fn ddmin<T: Clone>(mut items: Vec<T>, fails: &mut impl FnMut(&[T]) -> bool) -> Vec<T> {
+    let mut chunks = 2;
+
+    while !items.is_empty() {
+        let chunk_size = items.len().div_ceil(chunks);
+
+        let reduced = (0..items.len()).step_by(chunk_size).find_map(|start| {
+            let end = (start + chunk_size).min(items.len());
+            let complement: Vec<T> = items[..start]
+                .iter()
+                .chain(&items[end..])
+                .cloned()
+                .collect();
+
+            fails(&complement).then_some(complement)
+        });
+
+        match reduced {
+            Some(complement) => {
+                items = complement;
+                chunks = (chunks - 1).max(2);
+            }
+            None if chunks >= items.len() => break,
+            None => chunks = (chunks * 2).min(items.len()),
+        }
+    }
+
+    items
+}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::Result;
+    use common::{Puzzle, Variant};
+
+    use super::*;
+
+    struct Faulty;
+
+    impl Puzzle for Faulty {
+        type Input = Vec<Vec<u32>>;
+
+        const YEAR: u16 = 2015;
+        const DAY: u8 = 42;
+        const TITLE: &'static str = "Faulty";
+
+        fn parse(input: &str) -> Result<Self::Input> {
+            input
+                .lines()
+                .map(|line| {
+                    line.split_whitespace()
+                        .map(|num| Ok(num.parse()?))
+                        .collect()
+                })
+                .collect()
+        }
+
+        fn part1(input: &Self::Input) -> Result<String> {
+            Ok(input.iter().flatten().sum::<u32>().to_string())
+        }
+
+        fn part2(input: &Self::Input) -> Result<String> {
+            Ok(input.len().to_string())
+        }
+
+        fn generate(_size: usize, _seed: u64) -> String {
+            String::new()
+        }
+
+        fn variants() -> Vec<Variant<Self::Input>> {
+            vec![Variant {
+                part: 1,
+                name: "unlucky",
+                solve: |input| {
+                    let sum: u32 = input.iter().flatten().sum();
+                    let unlucky = input.iter().flatten().filter(|num| **num == 13).count();
+
+                    Ok((sum + unlucky as u32).to_string())
+                },
+            }]
+        }
+    }
+
+    #[test]
+    fn test_ddmin() {
+        let items: Vec<u32> = (0..20).collect();
+
+        let minimal = ddmin(items, &mut |items: &[u32]| {
+            items.contains(&3) && items.contains(&17)
+        });
+
+        assert_eq!(minimal, vec![3, 17]);
+    }
+
+    #[test]
+    fn test_tokens() {
+        assert_eq!(tokens("Card 1:  41 48"), vec!["Card", " 1:", "  41", " 48"]);
+        assert_eq!(tokens("  a"), vec!["  a"]);
+        assert_eq!(tokens("").concat(), "");
+    }
+
+    #[test]
+    fn test_crash_from_message() {
+        assert_eq!(
+            Crash::from_message("invalid digit in line 40"),
+            Crash::Error("invalid digit in line ".to_string())
+        );
+        assert_eq!(
+            Crash::from_message("invalid digit in line 40"),
+            Crash::from_message("invalid digit in line 2")
+        );
+        assert_ne!(
+            Crash::from_message("invalid digit in line 40"),
+            Crash::from_message("empty input")
+        );
+        assert_eq!(
+            Crash::from_message("Panicked: the len is 3 but the index is 5"),
+            Crash::from_message("Panicked: the len is 1 but the index is 2")
+        );
+    }
+
+    #[test]
+    fn test_minimize_mismatch() {
+        let entry = Entry::of::<Faulty>();
+        let input = "1 2 3\n4 13 5 6\n7 8\n9 10 13";
+
+        let (failure, minimized) = minimize(&entry, input).unwrap();
+
+        assert_eq!(failure, Failure::Mismatch { part: 1 });
+        assert_eq!(minimized.trim(), "13");
+    }
+
+    #[test]
+    fn test_minimize_parse_error() {
+        let entry = Entry::of::<Faulty>();
+        let input = "1 2 3\n4 x 5\n7 8";
+
+        let (failure, minimized) = minimize(&entry, input).unwrap();
+
+        assert_eq!(
+            failure,
+            Failure::Parse(Crash::Error("invalid digit found in string".to_string()))
+        );
+        assert_eq!(minimized.trim(), "x");
+    }
+
+    #[test]
+    fn test_minimize_passing_input() {
+        let entry = Entry::of::<Faulty>();
+
+        assert!(minimize(&entry, "1 2 3").is_none());
+    }
+}
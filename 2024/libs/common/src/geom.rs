@@ -0,0 +1,335 @@
+use std::fmt::{self, Display};
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+
+use anyhow::{bail, Error, Result};
+
+/// Position on a plane, `x` grows to the right and `y` downwards like the rows of a grid.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Point {
+    pub x: i64,
+    pub y: i64,
+}
+
+/// Offset between two points.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Vec2 {
+    pub x: i64,
+    pub y: i64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Direction {
+    Up,
+    Right,
+    Down,
+    Left,
+}
+
+impl Point {
+    pub const ORIGIN: Point = Point { x: 0, y: 0 };
+
+    pub const fn new(x: i64, y: i64) -> Self {
+        Self { x, y }
+    }
+
+    pub fn manhattan(self, other: Point) -> i64 {
+        (other - self).manhattan()
+    }
+
+    pub fn chebyshev(self, other: Point) -> i64 {
+        (other - self).chebyshev()
+    }
+
+    /// The four points next to this one.
+    pub fn neighbours4(self) -> impl Iterator<Item = Point> {
+        Direction::ALL
+            .into_iter()
+            .map(move |direction| self + direction)
+    }
+
+    /// The eight points around this one, diagonals included.
+    pub fn neighbours8(self) -> impl Iterator<Item = Point> {
+        (-1..=1)
+            .flat_map(|y| (-1..=1).map(move |x| Vec2::new(x, y)))
+            .filter(|offset| *offset != Vec2::ZERO)
+            .map(move |offset| self + offset)
+    }
+}
+
+impl Vec2 {
+    pub const ZERO: Vec2 = Vec2 { x: 0, y: 0 };
+
+    pub const fn new(x: i64, y: i64) -> Self {
+        Self { x, y }
+    }
+
+    pub fn manhattan(self) -> i64 {
+        self.x.abs() + self.y.abs()
+    }
+
+    pub fn chebyshev(self) -> i64 {
+        self.x.abs().max(self.y.abs())
+    }
+
+    /// Quarter turn counterclockwise, as seen on screen.
+    pub fn turn_left(self) -> Self {
+        Self::new(self.y, -self.x)
+    }
+
+    /// Quarter turn clockwise, as seen on screen.
+    pub fn turn_right(self) -> Self {
+        Self::new(-self.y, self.x)
+    }
+}
+
+impl Direction {
+    /// Clockwise, starting with `Up`.
+    pub const ALL: [Direction; 4] = [
+        Direction::Up,
+        Direction::Right,
+        Direction::Down,
+        Direction::Left,
+    ];
+
+    pub fn turn_left(self) -> Self {
+        match self {
+            Direction::Up => Direction::Left,
+            Direction::Right => Direction::Up,
+            Direction::Down => Direction::Right,
+            Direction::Left => Direction::Down,
+        }
+    }
+
+    pub fn turn_right(self) -> Self {
+        match self {
+            Direction::Up => Direction::Right,
+            Direction::Right => Direction::Down,
+            Direction::Down => Direction::Left,
+            Direction::Left => Direction::Up,
+        }
+    }
+
+    pub fn reverse(self) -> Self {
+        self.turn_left().turn_left()
+    }
+
+    pub fn offset(self) -> Vec2 {
+        match self {
+            Direction::Up => Vec2::new(0, -1),
+            Direction::Right => Vec2::new(1, 0),
+            Direction::Down => Vec2::new(0, 1),
+            Direction::Left => Vec2::new(-1, 0),
+        }
+    }
+}
+
+/// Arrows (`^>v<`), `UDLR` and compass letters (`NESW`), in either case.
+impl TryFrom<char> for Direction {
+    type Error = Error;
+
+    fn try_from(c: char) -> Result<Self> {
+        let direction = match c.to_ascii_uppercase() {
+            '^' | 'U' | 'N' => Direction::Up,
+            '>' | 'R' | 'E' => Direction::Right,
+            'V' | 'D' | 'S' => Direction::Down,
+            '<' | 'L' | 'W' => Direction::Left,
+            _ => bail!("Not a direction: {c}"),
+        };
+
+        Ok(direction)
+    }
+}
+
+impl From<Direction> for Vec2 {
+    fn from(direction: Direction) -> Self {
+        direction.offset()
+    }
+}
+
+impl Display for Point {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "({}, {})", self.x, self.y)
+    }
+}
+
+impl Display for Vec2 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "<{}, {}>", self.x, self.y)
+    }
+}
+
+impl Display for Direction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let arrow = match self {
+            Direction::Up => '^',
+            Direction::Right => '>',
+            Direction::Down => 'v',
+            Direction::Left => '<',
+        };
+
+        write!(f, "{arrow}")
+    }
+}
+
+impl Add<Vec2> for Point {
+    type Output = Point;
+
+    fn add(self, offset: Vec2) -> Point {
+        Point::new(self.x + offset.x, self.y + offset.y)
+    }
+}
+
+impl Add<Direction> for Point {
+    type Output = Point;
+
+    fn add(self, direction: Direction) -> Point {
+        self + direction.offset()
+    }
+}
+
+impl AddAssign<Vec2> for Point {
+    fn add_assign(&mut self, offset: Vec2) {
+        *self = *self + offset;
+    }
+}
+
+impl AddAssign<Direction> for Point {
+    fn add_assign(&mut self, direction: Direction) {
+        *self = *self + direction;
+    }
+}
+
+impl Sub<Vec2> for Point {
+    type Output = Point;
+
+    fn sub(self, offset: Vec2) -> Point {
+        self + -offset
+    }
+}
+
+impl SubAssign<Vec2> for Point {
+    fn sub_assign(&mut self, offset: Vec2) {
+        *self = *self - offset;
+    }
+}
+
+impl Sub for Point {
+    type Output = Vec2;
+
+    fn sub(self, other: Point) -> Vec2 {
+        Vec2::new(self.x - other.x, self.y - other.y)
+    }
+}
+
+impl Add for Vec2 {
+    type Output = Vec2;
+
+    fn add(self, other: Vec2) -> Vec2 {
+        Vec2::new(self.x + other.x, self.y + other.y)
+    }
+}
+
+impl AddAssign for Vec2 {
+    fn add_assign(&mut self, other: Vec2) {
+        *self = *self + other;
+    }
+}
+
+impl Sub for Vec2 {
+    type Output = Vec2;
+
+    fn sub(self, other: Vec2) -> Vec2 {
+        self + -other
+    }
+}
+
+impl SubAssign for Vec2 {
+    fn sub_assign(&mut self, other: Vec2) {
+        *self = *self - other;
+    }
+}
+
+impl Neg for Vec2 {
+    type Output = Vec2;
+
+    fn neg(self) -> Vec2 {
+        Vec2::new(-self.x, -self.y)
+    }
+}
+
+impl Mul<i64> for Vec2 {
+    type Output = Vec2;
+
+    fn mul(self, factor: i64) -> Vec2 {
+        Vec2::new(self.x * factor, self.y * factor)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_arithmetic() {
+        let mut point = Point::new(2, 3);
+
+        assert_eq!(point + Vec2::new(1, -1), Point::new(3, 2));
+        assert_eq!(point - Vec2::new(1, -1), Point::new(1, 4));
+        assert_eq!(Point::new(5, 5) - point, Vec2::new(3, 2));
+        assert_eq!(Vec2::new(1, 2) * 3 - Vec2::new(1, 1), Vec2::new(2, 5));
+        assert_eq!(-Vec2::new(1, -2), Vec2::new(-1, 2));
+
+        point += Direction::Up;
+        point += Vec2::new(1, 1) * 2;
+        assert_eq!(point, Point::new(4, 4));
+    }
+
+    #[test]
+    fn test_distances() {
+        let a = Point::new(1, 1);
+        let b = Point::new(4, -1);
+
+        assert_eq!(a.manhattan(b), 5);
+        assert_eq!(b.manhattan(a), 5);
+        assert_eq!(a.chebyshev(b), 3);
+    }
+
+    #[test]
+    fn test_turns() {
+        for direction in Direction::ALL {
+            assert_eq!(direction.turn_left().turn_right(), direction);
+            assert_eq!(
+                direction.offset().turn_right(),
+                direction.turn_right().offset()
+            );
+            assert_eq!(
+                direction.offset().turn_left(),
+                direction.turn_left().offset()
+            );
+            assert_eq!(direction.reverse().offset(), -direction.offset());
+        }
+
+        assert_eq!(Direction::Up.turn_right(), Direction::Right);
+        assert_eq!(Direction::Up.turn_left(), Direction::Left);
+    }
+
+    #[test]
+    fn test_from_char() {
+        let directions: Vec<Direction> = "^>v<".chars().map(|c| c.try_into().unwrap()).collect();
+
+        assert_eq!(directions, Direction::ALL);
+        assert_eq!(Direction::try_from('u').unwrap(), Direction::Up);
+        assert_eq!(Direction::try_from('W').unwrap(), Direction::Left);
+        assert!(Direction::try_from('x').is_err());
+        assert_eq!(Direction::Down.to_string(), "v");
+    }
+
+    #[test]
+    fn test_neighbours() {
+        let point = Point::new(0, 0);
+
+        assert_eq!(point.neighbours4().count(), 4);
+        assert!(point.neighbours8().all(|other| point.chebyshev(other) == 1));
+        assert_eq!(point.neighbours8().count(), 8);
+    }
+}
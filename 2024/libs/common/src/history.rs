@@ -0,0 +1,185 @@
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::Path;
+use std::process;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::puzzle::{Solution, DEFAULT_VARIANT};
+
+pub const HISTORY_FILE: &str = "data/history.jsonl";
+
+fn default_variant() -> String {
+    DEFAULT_VARIANT.to_string()
+}
+
+/// A single solved part, one line in the history file.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Record {
+    pub year: u16,
+    pub day: u8,
+    pub part: u8,
+    #[serde(default = "default_variant")]
+    pub variant: String,
+    pub answer: String,
+    pub duration_us: u64,
+    pub revision: String,
+    pub timestamp: u64,
+}
+
+impl Record {
+    pub fn from_solution(year: u16, day: u8, solution: &Solution) -> Vec<Record> {
+        let revision = git_revision();
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|duration| duration.as_secs())
+            .unwrap_or_default();
+
+        solution
+            .parts
+            .iter()
+            .map(|part| Record {
+                year,
+                day,
+                part: part.part,
+                variant: part.variant.to_string(),
+                answer: part.answer.clone(),
+                duration_us: part.duration.as_micros() as u64,
+                revision: revision.clone(),
+                timestamp,
+            })
+            .collect()
+    }
+}
+
+/// Short hash of `HEAD`, suffixed with `-dirty` for uncommitted changes.
+pub fn git_revision() -> String {
+    let output = process::Command::new("git")
+        .args(["rev-parse", "--short", "HEAD"])
+        .output();
+
+    let revision = match output {
+        Ok(output) if output.status.success() => {
+            String::from_utf8_lossy(&output.stdout).trim().to_string()
+        }
+        _ => return "unknown".to_string(),
+    };
+
+    let dirty = process::Command::new("git")
+        .args(["status", "--porcelain", "--untracked-files=no"])
+        .output()
+        .map(|output| !output.stdout.is_empty())
+        .unwrap_or_default();
+
+    if dirty {
+        format!("{revision}-dirty")
+    } else {
+        revision
+    }
+}
+
+pub fn append<P: AsRef<Path>>(path: P, records: &[Record]) -> Result<()> {
+    let path = path.as_ref();
+
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create {}", parent.display()))?;
+    }
+
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .with_context(|| format!("Failed to open {}", path.display()))?;
+
+    for record in records {
+        let line = serde_json::to_string(record).context("Failed to serialize record")?;
+        writeln!(file, "{line}").with_context(|| format!("Failed to write {}", path.display()))?;
+    }
+
+    Ok(())
+}
+
+pub fn load<P: AsRef<Path>>(path: P) -> Result<Vec<Record>> {
+    let path = path.as_ref();
+
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+
+    let content =
+        fs::read_to_string(path).with_context(|| format!("Failed to read {}", path.display()))?;
+
+    content
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(index, line)| {
+            serde_json::from_str(line)
+                .with_context(|| format!("Invalid record in line {}: {line}", index + 1))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env;
+    use std::time::Duration;
+
+    use super::*;
+    use crate::puzzle::PartSolution;
+
+    #[test]
+    fn test_append_and_load() {
+        let path = env::temp_dir().join(format!("aoc-history-{}.jsonl", process::id()));
+        let _ = fs::remove_file(&path);
+
+        let solution = Solution {
+            parse_duration: Duration::from_micros(3),
+            parts: vec![
+                PartSolution {
+                    part: 1,
+                    variant: DEFAULT_VARIANT,
+                    answer: "35".to_string(),
+                    duration: Duration::from_micros(120),
+                },
+                PartSolution {
+                    part: 2,
+                    variant: "fast",
+                    answer: "46".to_string(),
+                    duration: Duration::from_micros(4500),
+                },
+            ],
+        };
+
+        let records = Record::from_solution(2023, 5, &solution);
+        append(&path, &records).unwrap();
+        append(&path, &records[..1]).unwrap();
+
+        let loaded = load(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(loaded.len(), 3);
+        assert_eq!(loaded[0], records[0]);
+        assert_eq!(loaded[1].answer, "46");
+        assert_eq!(loaded[1].variant, "fast");
+        assert_eq!(loaded[1].duration_us, 4500);
+        assert_eq!(loaded[2], records[0]);
+    }
+
+    #[test]
+    fn test_load_without_variant() {
+        let line = r#"{"year":2023,"day":4,"part":1,"answer":"13","duration_us":5,"revision":"abc","timestamp":0}"#;
+
+        let record: Record = serde_json::from_str(line).unwrap();
+
+        assert_eq!(record.variant, DEFAULT_VARIANT);
+    }
+
+    #[test]
+    fn test_load_missing_file() {
+        assert!(load("does/not/exist.jsonl").unwrap().is_empty());
+    }
+}
@@ -0,0 +1,348 @@
+use std::fmt::{self, Debug, Display};
+
+/// Integer types intervals can be built from.
+pub trait Integer: Copy + Ord + Debug + Display {
+    const ONE: Self;
+
+    fn checked_add(self, other: Self) -> Option<Self>;
+
+    /// Lossless widening, every implementing type fits.
+    fn to_i128(self) -> i128;
+}
+
+macro_rules! impl_integer {
+    ($($int:ty),*) => {
+        $(
+            impl Integer for $int {
+                const ONE: Self = 1;
+
+                fn checked_add(self, other: Self) -> Option<Self> {
+                    <$int>::checked_add(self, other)
+                }
+
+                fn to_i128(self) -> i128 {
+                    self as i128
+                }
+            }
+        )*
+    };
+}
+
+impl_integer!(u8, u16, u32, u64, usize, i8, i16, i32, i64, isize);
+
+/// Half-open interval `[start, end)`, empty when `end <= start`.
+///
+/// Set operations only compare bounds and the constructors that add check for
+/// overflow, so intervals reaching the limits of `T` are handled safely.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Interval<T> {
+    pub start: T,
+    pub end: T,
+}
+
+impl<T: Integer> Interval<T> {
+    pub fn new(start: T, end: T) -> Self {
+        Self { start, end }
+    }
+
+    /// `[start, start + len)`, `None` if the end does not fit into `T`.
+    pub fn with_len(start: T, len: T) -> Option<Self> {
+        Some(Self::new(start, start.checked_add(len)?))
+    }
+
+    /// `[start, last]`, `None` if `last` is the largest value of `T`.
+    pub fn inclusive(start: T, last: T) -> Option<Self> {
+        Some(Self::new(start, last.checked_add(T::ONE)?))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.end <= self.start
+    }
+
+    /// Number of values in the interval, wide enough for any `T`.
+    pub fn len(&self) -> u128 {
+        if self.is_empty() {
+            0
+        } else {
+            (self.end.to_i128() - self.start.to_i128()) as u128
+        }
+    }
+
+    pub fn contains(&self, value: T) -> bool {
+        self.start <= value && value < self.end
+    }
+
+    pub fn intersects(&self, other: &Self) -> bool {
+        self.intersect(other).is_some()
+    }
+
+    /// Values in both intervals, `None` if there are none.
+    pub fn intersect(&self, other: &Self) -> Option<Self> {
+        let intersection = Self::new(self.start.max(other.start), self.end.min(other.end));
+
+        (!intersection.is_empty()).then_some(intersection)
+    }
+
+    /// Both intervals as one, `None` if there is a gap between them.
+    pub fn union(&self, other: &Self) -> Option<Self> {
+        if self.is_empty() {
+            return Some(*other);
+        }
+        if other.is_empty() {
+            return Some(*self);
+        }
+        if self.start > other.end || other.start > self.end {
+            return None;
+        }
+
+        Some(Self::new(
+            self.start.min(other.start),
+            self.end.max(other.end),
+        ))
+    }
+
+    /// Values of `self` that are not in `other`, the parts below and above `other`.
+    pub fn difference(&self, other: &Self) -> Vec<Self> {
+        if !self.intersects(other) {
+            return if self.is_empty() { vec![] } else { vec![*self] };
+        }
+
+        [
+            Self::new(self.start, other.start.min(self.end)),
+            Self::new(other.end.max(self.start), self.end),
+        ]
+        .into_iter()
+        .filter(|part| !part.is_empty())
+        .collect()
+    }
+
+    /// The values below `value` and the ones from `value` on.
+    pub fn split_at(&self, value: T) -> (Option<Self>, Option<Self>) {
+        let below = Self::new(self.start, value.min(self.end));
+        let above = Self::new(value.max(self.start), self.end);
+
+        (
+            (!below.is_empty()).then_some(below),
+            (!above.is_empty()).then_some(above),
+        )
+    }
+}
+
+impl<T: Display> Display for Interval<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "[{}, {})", self.start, self.end)
+    }
+}
+
+/// Set of values kept as sorted, disjoint, non-empty intervals.
+///
+/// Intervals that overlap or touch are merged, so two sets with the same
+/// values always compare equal.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct IntervalSet<T> {
+    intervals: Vec<Interval<T>>,
+}
+
+impl<T: Integer> Default for IntervalSet<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Integer> IntervalSet<T> {
+    pub fn new() -> Self {
+        Self {
+            intervals: Vec::new(),
+        }
+    }
+
+    fn normalise(mut intervals: Vec<Interval<T>>) -> Self {
+        intervals.retain(|interval| !interval.is_empty());
+        intervals.sort();
+
+        let mut normalised: Vec<Interval<T>> = Vec::with_capacity(intervals.len());
+        for interval in intervals {
+            match normalised.last_mut() {
+                Some(last) if interval.start <= last.end => last.end = last.end.max(interval.end),
+                _ => normalised.push(interval),
+            }
+        }
+
+        Self {
+            intervals: normalised,
+        }
+    }
+
+    pub fn insert(&mut self, interval: Interval<T>) {
+        let mut intervals = std::mem::take(&mut self.intervals);
+        intervals.push(interval);
+
+        *self = Self::normalise(intervals);
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.intervals.is_empty()
+    }
+
+    /// Number of values in the set.
+    pub fn len(&self) -> u128 {
+        self.intervals.iter().map(Interval::len).sum()
+    }
+
+    pub fn min(&self) -> Option<T> {
+        self.intervals.first().map(|interval| interval.start)
+    }
+
+    pub fn contains(&self, value: T) -> bool {
+        let index = self
+            .intervals
+            .partition_point(|interval| interval.end <= value);
+
+        self.intervals
+            .get(index)
+            .is_some_and(|interval| interval.contains(value))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Interval<T>> {
+        self.intervals.iter()
+    }
+
+    pub fn union(&self, other: &Self) -> Self {
+        Self::normalise(self.iter().chain(other.iter()).copied().collect())
+    }
+
+    pub fn intersection(&self, other: &Self) -> Self {
+        let intervals = self
+            .iter()
+            .flat_map(|a| other.iter().filter_map(|b| a.intersect(b)))
+            .collect();
+
+        Self::normalise(intervals)
+    }
+
+    pub fn difference(&self, other: &Self) -> Self {
+        let intervals = self
+            .iter()
+            .flat_map(|interval| {
+                other.iter().fold(vec![*interval], |parts, removed| {
+                    parts
+                        .iter()
+                        .flat_map(|part| part.difference(removed))
+                        .collect()
+                })
+            })
+            .collect();
+
+        Self::normalise(intervals)
+    }
+}
+
+impl<T: Integer> FromIterator<Interval<T>> for IntervalSet<T> {
+    fn from_iter<I: IntoIterator<Item = Interval<T>>>(iter: I) -> Self {
+        Self::normalise(iter.into_iter().collect())
+    }
+}
+
+impl<T: Integer> IntoIterator for IntervalSet<T> {
+    type Item = Interval<T>;
+    type IntoIter = std::vec::IntoIter<Interval<T>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.intervals.into_iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn interval(start: i64, end: i64) -> Interval<i64> {
+        Interval::new(start, end)
+    }
+
+    fn set(intervals: &[(i64, i64)]) -> IntervalSet<i64> {
+        intervals
+            .iter()
+            .map(|(start, end)| interval(*start, *end))
+            .collect()
+    }
+
+    #[test]
+    fn test_interval() {
+        assert_eq!(interval(2, 5).len(), 3);
+        assert_eq!(interval(5, 2).len(), 0);
+        assert!(interval(2, 5).contains(2));
+        assert!(!interval(2, 5).contains(5));
+        assert_eq!(Interval::with_len(250u8, 5), Some(Interval::new(250, 255)));
+        assert_eq!(Interval::with_len(250u8, 6), None);
+        assert_eq!(Interval::inclusive(0u8, 254), Some(Interval::new(0, 255)));
+        assert_eq!(Interval::inclusive(0u8, 255), None);
+        assert_eq!(Interval::new(i64::MIN, i64::MAX).len(), u64::MAX as u128);
+    }
+
+    #[test]
+    fn test_intersect_and_union() {
+        assert_eq!(
+            interval(0, 5).intersect(&interval(3, 8)),
+            Some(interval(3, 5))
+        );
+        assert_eq!(interval(0, 5).intersect(&interval(5, 8)), None);
+        assert_eq!(interval(0, 5).union(&interval(5, 8)), Some(interval(0, 8)));
+        assert_eq!(interval(0, 5).union(&interval(6, 8)), None);
+        assert_eq!(interval(0, 5).union(&interval(3, 3)), Some(interval(0, 5)));
+    }
+
+    #[test]
+    fn test_difference() {
+        assert_eq!(
+            interval(0, 10).difference(&interval(3, 5)),
+            vec![interval(0, 3), interval(5, 10)]
+        );
+        assert_eq!(
+            interval(0, 10).difference(&interval(-5, 5)),
+            vec![interval(5, 10)]
+        );
+        assert_eq!(interval(0, 10).difference(&interval(0, 10)), vec![]);
+        assert_eq!(
+            interval(0, 10).difference(&interval(10, 20)),
+            vec![interval(0, 10)]
+        );
+    }
+
+    #[test]
+    fn test_split_at() {
+        assert_eq!(
+            interval(0, 10).split_at(4),
+            (Some(interval(0, 4)), Some(interval(4, 10)))
+        );
+        assert_eq!(interval(0, 10).split_at(0), (None, Some(interval(0, 10))));
+        assert_eq!(interval(0, 10).split_at(12), (Some(interval(0, 10)), None));
+    }
+
+    #[test]
+    fn test_set_normalise() {
+        let set = set(&[(5, 8), (0, 2), (7, 10), (2, 3), (12, 12)]);
+
+        assert_eq!(
+            set.iter().copied().collect::<Vec<_>>(),
+            vec![interval(0, 3), interval(5, 10)]
+        );
+        assert_eq!(set.len(), 8);
+        assert_eq!(set.min(), Some(0));
+        assert!(set.contains(9));
+        assert!(!set.contains(3));
+        assert!(!set.contains(10));
+    }
+
+    #[test]
+    fn test_set_operations() {
+        let a = set(&[(0, 10), (20, 30)]);
+        let b = set(&[(5, 25)]);
+
+        assert_eq!(a.union(&b), set(&[(0, 30)]));
+        assert_eq!(a.intersection(&b), set(&[(5, 10), (20, 25)]));
+        assert_eq!(a.difference(&b), set(&[(0, 5), (25, 30)]));
+        assert_eq!(b.difference(&a), set(&[(10, 20)]));
+        assert!(a.difference(&a).is_empty());
+    }
+}
@@ -0,0 +1,527 @@
+use std::any::Any;
+use std::fmt::Debug;
+use std::fs;
+use std::panic::{self, AssertUnwindSafe};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use anyhow::{anyhow, bail, Context, Result};
+use log::{debug, warn};
+
+use crate::history::{self, Record, HISTORY_FILE};
+
+/// A daily puzzle with its stable `parse`/`part1`/`part2` API, used by the
+/// tooling that runs puzzles without going through their `Command`.
+pub trait Puzzle {
+    type Input;
+
+    const YEAR: u16;
+    const DAY: u8;
+    const TITLE: &'static str;
+
+    fn parse(input: &str) -> Result<Self::Input>;
+    fn part1(input: &Self::Input) -> Result<String>;
+    fn part2(input: &Self::Input) -> Result<String>;
+
+    /// Random input of roughly `size` entries, reproducible for a given `seed`.
+    fn generate(size: usize, seed: u64) -> String;
+
+    /// Alternative implementations of the parts, next to the default
+    /// `part1`/`part2`, e.g. a slow reference to cross check a fast solution.
+    fn variants() -> Vec<Variant<Self::Input>> {
+        Vec::new()
+    }
+}
+
+/// Name of the `part1`/`part2` implementation of a [`Puzzle`].
+pub const DEFAULT_VARIANT: &str = "default";
+
+/// A named implementation of one part of a puzzle.
+pub struct Variant<I> {
+    pub part: u8,
+    pub name: &'static str,
+    pub solve: fn(&I) -> Result<String>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct PartSolution {
+    pub part: u8,
+    pub variant: &'static str,
+    pub answer: String,
+    pub duration: Duration,
+}
+
+/// Outcome of a single variant in a cross check, errors and panics included.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Check {
+    pub part: u8,
+    pub variant: &'static str,
+    pub outcome: Result<String, String>,
+    pub duration: Duration,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Solution {
+    pub parse_duration: Duration,
+    pub parts: Vec<PartSolution>,
+}
+
+fn timed<T>(f: impl FnOnce() -> Result<T>) -> Result<(T, Duration)> {
+    let start = Instant::now();
+    let result = f()?;
+
+    Ok((result, start.elapsed()))
+}
+
+pub fn format_duration(duration: Duration) -> String {
+    let micros = duration.as_micros();
+
+    if micros < 1_000 {
+        format!("{micros}µs")
+    } else if micros < 1_000_000 {
+        format!("{:.2}ms", micros as f64 / 1_000.0)
+    } else {
+        format!("{:.2}s", micros as f64 / 1_000_000.0)
+    }
+}
+
+pub fn input_file(day: u8) -> String {
+    format!("data/puzzle_{day}/input.txt")
+}
+
+fn all_variants<P: Puzzle>() -> Vec<Variant<P::Input>> {
+    let mut variants = vec![
+        Variant {
+            part: 1,
+            name: DEFAULT_VARIANT,
+            solve: P::part1,
+        },
+        Variant {
+            part: 2,
+            name: DEFAULT_VARIANT,
+            solve: P::part2,
+        },
+    ];
+    variants.extend(P::variants());
+
+    variants
+}
+
+fn variant_names<P: Puzzle>() -> Vec<(u8, &'static str)> {
+    all_variants::<P>()
+        .iter()
+        .map(|variant| (variant.part, variant.name))
+        .collect()
+}
+
+/// Solves both parts, using the `variant` for every part that has one with that name.
+fn solve<P: Puzzle>(input: &str, variant: Option<&str>) -> Result<Solution> {
+    let variants = all_variants::<P>();
+
+    if let Some(name) = variant {
+        if !variants.iter().any(|variant| variant.name == name) {
+            let available: Vec<_> = variants.iter().map(|variant| variant.name).collect();
+            bail!(
+                "Unknown variant {name}, available: {}",
+                available.join(", ")
+            );
+        }
+    }
+
+    let (parsed, parse_duration) = timed(|| P::parse(input)).context("Failed to parse input")?;
+
+    let mut parts = Vec::new();
+    for part in [1, 2] {
+        let candidates: Vec<_> = variants
+            .iter()
+            .filter(|variant| variant.part == part)
+            .collect();
+        let selected = candidates
+            .iter()
+            .find(|candidate| Some(candidate.name) == variant)
+            .or_else(|| {
+                candidates
+                    .iter()
+                    .find(|candidate| candidate.name == DEFAULT_VARIANT)
+            })
+            .with_context(|| format!("No implementation for part {part}"))?;
+
+        let (answer, duration) = timed(|| (selected.solve)(&parsed))
+            .with_context(|| format!("Failed to solve part {part} ({})", selected.name))?;
+
+        parts.push(PartSolution {
+            part,
+            variant: selected.name,
+            answer,
+            duration,
+        });
+    }
+
+    Ok(Solution {
+        parse_duration,
+        parts,
+    })
+}
+
+fn panic_message(payload: Box<dyn Any + Send>) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        message.to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "unknown panic".to_string()
+    }
+}
+
+/// Runs `f`, turning a panic into an error.
+pub fn catch_panic<T>(f: impl FnOnce() -> Result<T>) -> Result<T> {
+    match panic::catch_unwind(AssertUnwindSafe(f)) {
+        Ok(result) => result,
+        Err(payload) => Err(anyhow!("Panicked: {}", panic_message(payload))),
+    }
+}
+
+/// Runs every variant of every part on the same parsed input.
+fn crosscheck<P: Puzzle>(input: &str) -> Result<Vec<Check>> {
+    let parsed = catch_panic(|| P::parse(input)).context("Failed to parse input")?;
+
+    let checks = all_variants::<P>()
+        .iter()
+        .map(|variant| {
+            let start = Instant::now();
+            let outcome =
+                catch_panic(|| (variant.solve)(&parsed)).map_err(|error| format!("{error:#}"));
+
+            Check {
+                part: variant.part,
+                variant: variant.name,
+                outcome,
+                duration: start.elapsed(),
+            }
+        })
+        .collect();
+
+    Ok(checks)
+}
+
+/// Parts whose variants failed or did not all give the same answer.
+pub fn disagreements(checks: &[Check]) -> Vec<u8> {
+    let mut parts: Vec<_> = checks.iter().map(|check| check.part).collect();
+    parts.sort();
+    parts.dedup();
+
+    parts
+        .into_iter()
+        .filter(|part| {
+            let outcomes: Vec<_> = checks
+                .iter()
+                .filter(|check| check.part == *part)
+                .map(|check| &check.outcome)
+                .collect();
+
+            outcomes.iter().any(|outcome| outcome.is_err())
+                || outcomes.windows(2).any(|pair| pair[0] != pair[1])
+        })
+        .collect()
+}
+
+/// Cross checks the puzzle on every `*.txt` in `dir`, where `aoc minimize`
+/// leaves its reproducers. Fails with every fixture that does not parse or on
+/// which variants fail or disagree, a missing `dir` has no fixtures.
+pub fn check_fixtures<P: Puzzle>(dir: impl AsRef<Path>) -> Result<()> {
+    let dir = dir.as_ref();
+    if !dir.exists() {
+        return Ok(());
+    }
+
+    let mut paths = fs::read_dir(dir)
+        .with_context(|| format!("Failed to read {}", dir.display()))?
+        .map(|entry| entry.map(|entry| entry.path()))
+        .collect::<Result<Vec<PathBuf>, _>>()?;
+    paths.retain(|path| path.extension().is_some_and(|extension| extension == "txt"));
+    paths.sort();
+
+    let mut failures = Vec::new();
+    for path in &paths {
+        let input = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read fixture {}", path.display()))?;
+
+        match crosscheck::<P>(&input) {
+            Ok(checks) => {
+                for part in disagreements(&checks) {
+                    let outcomes: Vec<_> = checks
+                        .iter()
+                        .filter(|check| check.part == part)
+                        .map(|check| match &check.outcome {
+                            Ok(answer) => format!("{} = {answer}", check.variant),
+                            Err(error) => format!("{} failed: {error}", check.variant),
+                        })
+                        .collect();
+
+                    failures.push(format!(
+                        "{} part {part}: {}",
+                        path.display(),
+                        outcomes.join(", ")
+                    ));
+                }
+            }
+            Err(error) => failures.push(format!("{}: {error:#}", path.display())),
+        }
+    }
+
+    if !failures.is_empty() {
+        bail!(
+            "{} of {} fixtures failed:\n{}",
+            failures.len(),
+            paths.len(),
+            failures.join("\n")
+        );
+    }
+
+    Ok(())
+}
+
+/// Solves the puzzle on its input file, prints the answers and appends them
+/// to the run history.
+pub fn run<P: Puzzle>(variant: Option<&str>) -> Result<Solution> {
+    let input_file = input_file(P::DAY);
+
+    let input = fs::read_to_string(&input_file)
+        .with_context(|| format!("Failed to read input file {input_file}"))?;
+
+    let solution = solve::<P>(&input, variant)?;
+
+    debug!("Parsing took {}", format_duration(solution.parse_duration));
+    for part in &solution.parts {
+        debug!(
+            "Part {} ({}) took {}",
+            part.part,
+            part.variant,
+            format_duration(part.duration)
+        );
+        println!("Part {}: {}", part.part, part.answer);
+    }
+
+    let records = Record::from_solution(P::YEAR, P::DAY, &solution);
+    if let Err(error) = history::append(HISTORY_FILE, &records) {
+        warn!("Failed to record run history: {error:#}");
+    }
+
+    Ok(solution)
+}
+
+/// Type erased [`Puzzle`], so puzzles with different inputs can live in one registry.
+#[derive(Clone, Copy)]
+pub struct Entry {
+    pub year: u16,
+    pub day: u8,
+    pub title: &'static str,
+    solve: fn(&str, Option<&str>) -> Result<Solution>,
+    crosscheck: fn(&str) -> Result<Vec<Check>>,
+    variants: fn() -> Vec<(u8, &'static str)>,
+    generate: fn(usize, u64) -> String,
+}
+
+impl Entry {
+    pub fn of<P: Puzzle>() -> Self {
+        Self {
+            year: P::YEAR,
+            day: P::DAY,
+            title: P::TITLE,
+            solve: solve::<P>,
+            crosscheck: crosscheck::<P>,
+            variants: variant_names::<P>,
+            generate: P::generate,
+        }
+    }
+
+    pub fn solve(&self, input: &str) -> Result<Solution> {
+        (self.solve)(input, None)
+    }
+
+    pub fn solve_variant(&self, input: &str, variant: Option<&str>) -> Result<Solution> {
+        (self.solve)(input, variant)
+    }
+
+    pub fn crosscheck(&self, input: &str) -> Result<Vec<Check>> {
+        (self.crosscheck)(input)
+    }
+
+    /// Part and name of every implementation, defaults included.
+    pub fn variants(&self) -> Vec<(u8, &'static str)> {
+        (self.variants)()
+    }
+
+    pub fn generate(&self, size: usize, seed: u64) -> String {
+        (self.generate)(size, seed)
+    }
+}
+
+impl Debug for Entry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Entry")
+            .field("year", &self.year)
+            .field("day", &self.day)
+            .field("title", &self.title)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Sum;
+
+    impl Puzzle for Sum {
+        type Input = Vec<u32>;
+
+        const YEAR: u16 = 2015;
+        const DAY: u8 = 42;
+        const TITLE: &'static str = "Sum";
+
+        fn parse(input: &str) -> Result<Self::Input> {
+            input
+                .split_whitespace()
+                .map(|num| num.parse().context("Not a number"))
+                .collect()
+        }
+
+        fn part1(input: &Self::Input) -> Result<String> {
+            Ok(input.iter().sum::<u32>().to_string())
+        }
+
+        fn part2(input: &Self::Input) -> Result<String> {
+            Ok(input.iter().product::<u32>().to_string())
+        }
+
+        fn generate(size: usize, seed: u64) -> String {
+            (0..size)
+                .map(|i| (seed + i as u64).to_string())
+                .collect::<Vec<_>>()
+                .join(" ")
+        }
+
+        fn variants() -> Vec<Variant<Self::Input>> {
+            vec![
+                Variant {
+                    part: 1,
+                    name: "fold",
+                    solve: |input| Ok(input.iter().rev().sum::<u32>().to_string()),
+                },
+                Variant {
+                    part: 2,
+                    name: "broken",
+                    solve: |input| Ok(input.iter().sum::<u32>().to_string()),
+                },
+                Variant {
+                    part: 2,
+                    name: "panicking",
+                    solve: |_| panic!("not implemented"),
+                },
+            ]
+        }
+    }
+
+    #[test]
+    fn test_entry_solve() {
+        let entry = Entry::of::<Sum>();
+
+        assert_eq!(entry.day, 42);
+        assert_eq!(entry.title, "Sum");
+
+        let solution = entry.solve("1 2 3 4").unwrap();
+        let answers: Vec<_> = solution
+            .parts
+            .iter()
+            .map(|part| (part.part, part.answer.as_str()))
+            .collect();
+
+        assert_eq!(answers, vec![(1, "10"), (2, "24")]);
+    }
+
+    #[test]
+    fn test_format_duration() {
+        assert_eq!(format_duration(Duration::from_micros(999)), "999µs");
+        assert_eq!(format_duration(Duration::from_micros(1_500)), "1.50ms");
+        assert_eq!(format_duration(Duration::from_millis(2_250)), "2.25s");
+    }
+
+    #[test]
+    fn test_entry_solve_variant() {
+        let entry = Entry::of::<Sum>();
+
+        let solution = entry.solve_variant("1 2 3 4", Some("broken")).unwrap();
+        let answers: Vec<_> = solution
+            .parts
+            .iter()
+            .map(|part| (part.part, part.variant, part.answer.as_str()))
+            .collect();
+
+        assert_eq!(answers, vec![(1, "default", "10"), (2, "broken", "10")]);
+        assert!(entry.solve_variant("1 2 3 4", Some("unknown")).is_err());
+    }
+
+    #[test]
+    fn test_entry_crosscheck() {
+        let entry = Entry::of::<Sum>();
+
+        assert_eq!(
+            entry.variants(),
+            vec![
+                (1, "default"),
+                (2, "default"),
+                (1, "fold"),
+                (2, "broken"),
+                (2, "panicking")
+            ]
+        );
+
+        let checks = entry.crosscheck("1 2 3 4").unwrap();
+
+        assert_eq!(checks.len(), 5);
+        assert_eq!(checks[2].outcome, Ok("10".to_string()));
+        assert_eq!(
+            checks[4].outcome,
+            Err("Panicked: not implemented".to_string())
+        );
+        assert_eq!(disagreements(&checks), vec![2]);
+        assert!(disagreements(&checks[..3]).is_empty());
+    }
+
+    #[test]
+    fn test_entry_generate() {
+        let entry = Entry::of::<Sum>();
+
+        assert_eq!(entry.generate(3, 5), "5 6 7");
+    }
+
+    #[test]
+    fn test_entry_solve_parse_error() {
+        let entry = Entry::of::<Sum>();
+
+        assert!(entry.solve("1 two 3").is_err());
+    }
+
+    #[test]
+    fn test_check_fixtures() {
+        let dir = std::env::temp_dir().join(format!("aoc-fixtures-{}", std::process::id()));
+
+        assert!(check_fixtures::<Sum>(&dir).is_ok());
+
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("notes.md"), "not a fixture").unwrap();
+        assert!(check_fixtures::<Sum>(&dir).is_ok());
+
+        fs::write(dir.join("minimized_1.txt"), "1 x").unwrap();
+        fs::write(dir.join("minimized_2.txt"), "2 2").unwrap();
+        let error = check_fixtures::<Sum>(&dir).unwrap_err().to_string();
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert!(error.starts_with("2 of 2 fixtures failed:"));
+        assert!(error.contains("minimized_1.txt: Failed to parse input"));
+        assert!(error.contains(
+            "minimized_2.txt part 2: default = 4, broken = 4, panicking failed: Panicked: not implemented"
+        ));
+    }
+}
@@ -0,0 +1,194 @@
+//! Inputs made of sections separated by blank lines, each starting with a
+//! header line, like the almanac of 2023 day 5.
+
+use anyhow::{Context, Result};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Section<'a> {
+    pub header: &'a str,
+    /// Number of the header line, starting at 1.
+    pub line: usize,
+    pub body: Vec<&'a str>,
+}
+
+impl<'a> Section<'a> {
+    /// Body lines together with their line numbers.
+    pub fn numbered(&self) -> impl Iterator<Item = (usize, &'a str)> + '_ {
+        self.body
+            .iter()
+            .enumerate()
+            .map(|(index, line)| (self.line + 1 + index, *line))
+    }
+}
+
+/// Splits `input` at blank lines, all lines are trimmed.
+pub fn sections(input: &str) -> Vec<Section<'_>> {
+    let mut sections = Vec::new();
+    let mut current: Option<Section> = None;
+
+    for (index, line) in input.lines().enumerate() {
+        let line = line.trim();
+
+        if line.is_empty() {
+            sections.extend(current.take());
+            continue;
+        }
+
+        match &mut current {
+            Some(section) => section.body.push(line),
+            None => {
+                current = Some(Section {
+                    header: line,
+                    line: index + 1,
+                    body: Vec::new(),
+                })
+            }
+        }
+    }
+    sections.extend(current);
+
+    sections
+}
+
+type Handler<'a, T> = Box<dyn Fn(&Section<'a>) -> Option<Result<T>> + 'a>;
+
+/// Parses every section with the first handler that recognises its header.
+pub struct Dispatcher<'a, T> {
+    handlers: Vec<Handler<'a, T>>,
+}
+
+impl<T> Default for Dispatcher<'_, T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<'a, T> Dispatcher<'a, T> {
+    pub fn new() -> Self {
+        Self {
+            handlers: Vec::new(),
+        }
+    }
+
+    /// Hands sections whose header `matcher` accepts to `handler`, together
+    /// with whatever the matcher extracted from the header.
+    pub fn on<M, FM, FH>(mut self, matcher: FM, handler: FH) -> Self
+    where
+        FM: Fn(&'a str) -> Option<M> + 'a,
+        FH: Fn(M, &Section<'a>) -> Result<T> + 'a,
+    {
+        self.handlers.push(Box::new(move |section| {
+            matcher(section.header).map(|matched| handler(matched, section))
+        }));
+
+        self
+    }
+
+    /// Every section parsed in order, fails on a section no handler recognises.
+    pub fn parse(&self, input: &'a str) -> Result<Vec<T>> {
+        sections(input)
+            .iter()
+            .map(|section| {
+                let parsed = self
+                    .handlers
+                    .iter()
+                    .find_map(|handler| handler(section))
+                    .with_context(|| {
+                        format!(
+                            "Unexpected section in line {}: {}",
+                            section.line, section.header
+                        )
+                    })?;
+
+                parsed.with_context(|| {
+                    format!(
+                        "Failed to parse section in line {}: {}",
+                        section.line, section.header
+                    )
+                })
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::bail;
+
+    use super::*;
+
+    #[test]
+    fn test_sections() {
+        let input = "seeds: 1 2\n\n\n  a map:\n  1 2 3\n   \n4 5 6\nb map:\n";
+
+        assert_eq!(
+            sections(input),
+            vec![
+                Section {
+                    header: "seeds: 1 2",
+                    line: 1,
+                    body: vec![],
+                },
+                Section {
+                    header: "a map:",
+                    line: 4,
+                    body: vec!["1 2 3"],
+                },
+                Section {
+                    header: "4 5 6",
+                    line: 7,
+                    body: vec!["b map:"],
+                },
+            ]
+        );
+        assert_eq!(
+            sections(input)[1].numbered().collect::<Vec<_>>(),
+            vec![(5, "1 2 3")]
+        );
+        assert!(sections("\n \n").is_empty());
+    }
+
+    #[derive(Debug, PartialEq)]
+    enum Block<'a> {
+        Seeds(&'a str),
+        Map(&'a str, usize),
+    }
+
+    fn dispatcher<'a>() -> Dispatcher<'a, Block<'a>> {
+        Dispatcher::new()
+            .on(
+                |header: &'a str| header.strip_prefix("seeds: "),
+                |seeds, _| Ok(Block::Seeds(seeds)),
+            )
+            .on(
+                |header: &'a str| header.strip_suffix(" map:"),
+                |name, section| {
+                    if section.body.is_empty() {
+                        bail!("Empty map");
+                    }
+                    Ok(Block::Map(name, section.body.len()))
+                },
+            )
+    }
+
+    #[test]
+    fn test_dispatch() {
+        let blocks = dispatcher()
+            .parse("seeds: 1 2\n\na map:\n1 2 3\n4 5 6")
+            .unwrap();
+
+        assert_eq!(blocks, vec![Block::Seeds("1 2"), Block::Map("a", 2)]);
+    }
+
+    #[test]
+    fn test_dispatch_errors() {
+        let error = dispatcher().parse("seeds: 1\n\nwhat:\n1").unwrap_err();
+        assert_eq!(error.to_string(), "Unexpected section in line 3: what:");
+
+        let error = dispatcher().parse("a map:").unwrap_err();
+        assert_eq!(
+            format!("{error:#}"),
+            "Failed to parse section in line 1: a map:: Empty map"
+        );
+    }
+}
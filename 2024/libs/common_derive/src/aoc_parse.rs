@@ -0,0 +1,244 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{Data, DeriveInput, Fields, LitStr};
+
+#[derive(Debug, PartialEq)]
+enum Segment {
+    Literal(String),
+    Field {
+        name: String,
+        separator: Option<String>,
+    },
+}
+
+/// Splits a pattern like `Game {id}: {rounds:;}` into literals and fields.
+fn parse_format(format: &str) -> Result<Vec<Segment>, String> {
+    let mut segments = Vec::new();
+    let mut literal = String::new();
+    let mut chars = format.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '{' if chars.peek() == Some(&'{') => {
+                chars.next();
+                literal.push('{');
+            }
+            '}' if chars.peek() == Some(&'}') => {
+                chars.next();
+                literal.push('}');
+            }
+            '{' => {
+                let placeholder: String = chars.by_ref().take_while(|c| *c != '}').collect();
+                let (name, separator) = match placeholder.split_once(':') {
+                    Some((name, separator)) => (name, Some(separator.to_string())),
+                    None => (placeholder.as_str(), None),
+                };
+
+                if name.is_empty() {
+                    return Err(format!("Placeholder without a field name in `{format}`"));
+                }
+                if separator.as_deref() == Some("") {
+                    return Err(format!("Empty separator for field `{name}`"));
+                }
+                if !literal.is_empty() {
+                    segments.push(Segment::Literal(std::mem::take(&mut literal)));
+                } else if matches!(segments.last(), Some(Segment::Field { .. })) {
+                    return Err(format!(
+                        "Field `{name}` needs text between it and the field before"
+                    ));
+                }
+
+                segments.push(Segment::Field {
+                    name: name.to_string(),
+                    separator,
+                });
+            }
+            '}' => return Err(format!("Unmatched `}}` in `{format}`, use `}}}}`")),
+            c => literal.push(c),
+        }
+    }
+
+    if !literal.is_empty() {
+        segments.push(Segment::Literal(literal));
+    }
+
+    Ok(segments)
+}
+
+fn format_attribute(input: &DeriveInput) -> syn::Result<LitStr> {
+    let mut format = None;
+
+    for attribute in input
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("aoc"))
+    {
+        attribute.parse_nested_meta(|meta| {
+            if meta.path.is_ident("fmt") {
+                format = Some(meta.value()?.parse::<LitStr>()?);
+                Ok(())
+            } else {
+                Err(meta.error("Expected `fmt = \"...\"`"))
+            }
+        })?;
+    }
+
+    format.ok_or_else(|| syn::Error::new_spanned(&input.ident, "Missing #[aoc(fmt = \"...\")]"))
+}
+
+pub fn expand(input: &DeriveInput) -> syn::Result<TokenStream> {
+    let Data::Struct(data) = &input.data else {
+        return Err(syn::Error::new_spanned(
+            &input.ident,
+            "AocParse only supports structs",
+        ));
+    };
+    let Fields::Named(fields) = &data.fields else {
+        return Err(syn::Error::new_spanned(
+            &input.ident,
+            "AocParse needs named fields",
+        ));
+    };
+
+    let format = format_attribute(input)?;
+    let segments =
+        parse_format(&format.value()).map_err(|error| syn::Error::new_spanned(&format, error))?;
+
+    let names: Vec<String> = segments
+        .iter()
+        .filter_map(|segment| match segment {
+            Segment::Field { name, .. } => Some(name.clone()),
+            Segment::Literal(_) => None,
+        })
+        .collect();
+
+    for field in &fields.named {
+        let ident = field.ident.as_ref().expect("named field");
+        let count = names.iter().filter(|name| ident == *name).count();
+
+        if count != 1 {
+            return Err(syn::Error::new_spanned(
+                &format,
+                format!("Field `{ident}` has to appear exactly once in the pattern, found {count}"),
+            ));
+        }
+    }
+    if let Some(unknown) = names.iter().find(|name| {
+        !fields
+            .named
+            .iter()
+            .any(|field| field.ident.as_ref().is_some_and(|ident| ident == *name))
+    }) {
+        return Err(syn::Error::new_spanned(
+            &format,
+            format!("No field named `{unknown}`"),
+        ));
+    }
+
+    let mut steps = Vec::new();
+    let mut index = 0;
+    while index < segments.len() {
+        match &segments[index] {
+            Segment::Literal(literal) => {
+                steps.push(quote! {
+                    let rest = ::common::parse::derive::literal(input, rest, #literal)?;
+                });
+            }
+            Segment::Field { name, separator } => {
+                let ident = syn::Ident::new(name, format.span());
+                let ty = &fields
+                    .named
+                    .iter()
+                    .find(|field| field.ident.as_ref().is_some_and(|field| field == name))
+                    .expect("checked above")
+                    .ty;
+
+                // The text after a field ends it, the last field takes the rest.
+                let text = match segments.get(index + 1) {
+                    Some(Segment::Literal(next)) => {
+                        index += 1;
+                        quote! {
+                            let (text, rest) = ::common::parse::derive::until(input, rest, #name, #next)?;
+                        }
+                    }
+                    _ => quote! {
+                        let (text, rest) = (rest, "");
+                    },
+                };
+                let value = match separator {
+                    Some(separator) => quote! {
+                        ::common::parse::derive::items::<#ty, _>(#name, text, #separator)?
+                    },
+                    None => quote! {
+                        ::common::parse::derive::field::<#ty>(#name, text)?
+                    },
+                };
+
+                steps.push(quote! {
+                    #text
+                    let #ident: #ty = #value;
+                });
+            }
+        }
+        index += 1;
+    }
+
+    let ident = &input.ident;
+    let (impl_generics, type_generics, where_clause) = input.generics.split_for_impl();
+    let field_names = fields.named.iter().map(|field| &field.ident);
+
+    Ok(quote! {
+        impl #impl_generics ::std::str::FromStr for #ident #type_generics #where_clause {
+            type Err = ::anyhow::Error;
+
+            fn from_str(input: &str) -> ::anyhow::Result<Self> {
+                let rest = input.trim();
+                #(#steps)*
+                ::common::parse::derive::end(input, rest)?;
+
+                Ok(Self { #(#field_names),* })
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn field(name: &str, separator: Option<&str>) -> Segment {
+        Segment::Field {
+            name: name.to_string(),
+            separator: separator.map(str::to_string),
+        }
+    }
+
+    fn literal(text: &str) -> Segment {
+        Segment::Literal(text.to_string())
+    }
+
+    #[test]
+    fn test_parse_format() {
+        assert_eq!(
+            parse_format("Game {id}: {rounds:;}").unwrap(),
+            vec![
+                literal("Game "),
+                field("id", None),
+                literal(": "),
+                field("rounds", Some(";"))
+            ]
+        );
+        assert_eq!(
+            parse_format("{{{x}}}").unwrap(),
+            vec![literal("{"), field("x", None), literal("}")]
+        );
+    }
+
+    #[test]
+    fn test_parse_format_errors() {
+        assert!(parse_format("{a}{b}").is_err());
+        assert!(parse_format("{}").is_err());
+        assert!(parse_format("{a:}").is_err());
+        assert!(parse_format("a}").is_err());
+    }
+}
@@ -0,0 +1,41 @@
+//! Derive macros re-exported by `common`, the generated code refers to helpers
+//! in there and is only usable in crates depending on it.
+
+mod aoc_parse;
+mod cell;
+
+use proc_macro::TokenStream;
+use syn::{parse_macro_input, DeriveInput};
+
+/// `FromStr` for a struct from a line pattern given as `#[aoc(fmt = "...")]`,
+/// like `#[aoc(fmt = "Game {id}: {rounds:;}")]`.
+///
+/// `{field}` parses the field with its own `FromStr`, `{field:SEP}` splits the
+/// text at `SEP` and collects the trimmed, non-empty items into the field. Text
+/// between placeholders has to match literally, `{{` and `}}` escape braces.
+/// Each field ends at the first occurrence of the text following it. Errors
+/// name the field that failed.
+#[proc_macro_derive(AocParse, attributes(aoc))]
+pub fn derive_aoc_parse(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    aoc_parse::expand(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// `common::grid::Cell` and `Display` for an enum from a character legend, so
+/// grids of it parse with `Grid::parse_cells` and print back as they were read.
+///
+/// Unit variants take one character each, `#[cell('#')] Wall`. A single
+/// `#[cell(digit)] Digit(u8)` takes the ASCII digits with their value, and a
+/// single `#[cell(other)] Symbol(char)` everything no other variant took.
+/// Without the latter, unknown characters are an error.
+#[proc_macro_derive(Cell, attributes(cell))]
+pub fn derive_cell(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    cell::expand(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
@@ -0,0 +1,42 @@
+use common::rng::Rng;
+
+/// Generates `size` pairs of location ids, with some ids shared between both lists.
+pub fn generate(size: usize, seed: u64) -> String {
+    let mut rng = Rng::new(seed);
+
+    let left: Vec<_> = (0..size).map(|_| rng.range(10_000..100_000)).collect();
+    let right: Vec<_> = (0..size)
+        .map(|_| {
+            if !left.is_empty() && rng.chance(0.3) {
+                *rng.choose(&left)
+            } else {
+                rng.range(10_000..100_000)
+            }
+        })
+        .collect();
+
+    left.iter()
+        .zip(right.iter())
+        .map(|(l, r)| format!("{l}   {r}"))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_generate() {
+        let input = generate(100, 1);
+
+        assert_eq!(input, generate(100, 1));
+        assert_ne!(input, generate(100, 2));
+
+        let input = crate::parse(&input).unwrap();
+        assert_eq!(input.left.len(), 100);
+        assert_eq!(input.right.len(), 100);
+        assert!(crate::part1(&input).is_ok());
+        assert!(crate::part2(&input).is_ok());
+    }
+}
@@ -1,29 +1,108 @@
 #![feature(vec_pop_if)]
-mod part1;
-mod part2;
+pub mod generator;
+pub mod part1;
+pub mod part2;
 
-use std::fs::File;
-
-use anyhow::Result;
+use anyhow::{Context, Result};
 
 use clap::Parser;
 
+/// The two location id lists, left and right column of the input.
+#[derive(Debug, PartialEq, Clone)]
+pub struct Input {
+    pub left: Vec<usize>,
+    pub right: Vec<usize>,
+}
+
+pub fn parse(input: &str) -> Result<Input> {
+    let mut left = Vec::new();
+    let mut right = Vec::new();
+
+    for line in input.lines() {
+        let mut words = line.split_whitespace();
+
+        let l = words.next().context("No left")?;
+        let l = l
+            .parse::<usize>()
+            .with_context(|| format!("Failed to parse {l}"))?;
+
+        let r = words.next().context("No right")?;
+        let r = r
+            .parse::<usize>()
+            .with_context(|| format!("Failed to parse {r}"))?;
+
+        left.push(l);
+        right.push(r);
+    }
+
+    Ok(Input { left, right })
+}
+
+pub fn part1(input: &Input) -> Result<isize> {
+    let to_signed = |list: &[usize]| -> Result<Vec<isize>> {
+        list.iter()
+            .map(|v| isize::try_from(*v).context("Location id too large"))
+            .collect()
+    };
+
+    let mut left = to_signed(&input.left)?;
+    let mut right = to_signed(&input.right)?;
+
+    part1::distance(&mut left, &mut right)
+}
+
+pub fn part2(input: &Input) -> Result<usize> {
+    part2::similarity(input.left.clone(), input.right.clone())
+}
+
+pub struct Solver;
+
+impl common::Puzzle for Solver {
+    type Input = Input;
+
+    const YEAR: u16 = 2024;
+    const DAY: u8 = 1;
+    const TITLE: &'static str = "Historian Hysteria";
+
+    fn parse(input: &str) -> Result<Input> {
+        parse(input)
+    }
+
+    fn part1(input: &Input) -> Result<String> {
+        part1(input).map(|answer| answer.to_string())
+    }
+
+    fn part2(input: &Input) -> Result<String> {
+        part2(input).map(|answer| answer.to_string())
+    }
+
+    fn generate(size: usize, seed: u64) -> String {
+        generator::generate(size, seed)
+    }
+}
+
 #[derive(Parser, Debug)]
-pub struct Command {}
+pub struct Command {
+    /// Run a named solution variant instead of the default one
+    #[arg(long)]
+    variant: Option<String>,
+}
 
 impl common::CommandRunner for Command {
     fn run(&self) -> Result<()> {
-        let input_file = "data/puzzle_1/input.txt";
-
-        let file = File::open(input_file)?;
+        common::run::<Solver>(self.variant.as_deref())?;
 
-        let part1_result = part1::run(file)?;
-        println!("Part 1: {part1_result}");
+        Ok(())
+    }
+}
 
-        let file = File::open(input_file)?;
-        let part2_result = part2::run(file)?;
-        println!("Part 2: {part2_result}");
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-        Ok(())
+    #[test]
+    fn test_fixtures() {
+        common::puzzle::check_fixtures::<Solver>(concat!(env!("CARGO_MANIFEST_DIR"), "/fixtures"))
+            .unwrap();
     }
 }
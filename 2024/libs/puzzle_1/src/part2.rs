@@ -1,34 +1,6 @@
-use std::{
-    fs::File,
-    io::{BufRead, BufReader},
-};
+use anyhow::Result;
 
-use anyhow::{Context, Result};
-
-pub fn run(file: File) -> Result<usize> {
-    let mut left_list = Vec::new();
-    let mut right_list = Vec::new();
-
-    let file = BufReader::new(file);
-
-    for line in file.lines() {
-        let line = line?;
-        let mut words = line.split_whitespace();
-
-        let left = words.next().context("No left")?;
-        let left = left.parse::<usize>()?;
-
-        let right = words.next().context("No right")?;
-        let right = right.parse::<usize>()?;
-
-        left_list.push(left);
-        right_list.push(right);
-    }
-
-    similarity(left_list, right_list)
-}
-
-fn similarity(left: Vec<usize>, right: Vec<usize>) -> Result<usize> {
+pub fn similarity(left: Vec<usize>, right: Vec<usize>) -> Result<usize> {
     let mut left = left;
     let mut right = right;
 
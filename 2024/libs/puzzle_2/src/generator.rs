@@ -0,0 +1,63 @@
+use common::rng::Rng;
+
+fn generate_report(rng: &mut Rng) -> Vec<u64> {
+    let len = rng.range(5..9) as usize;
+
+    let mut level = rng.range(1..60);
+    let mut report = vec![level];
+    for _ in 1..len {
+        level += rng.range(1..4);
+        report.push(level);
+    }
+
+    if rng.chance(0.5) {
+        report.reverse();
+    }
+
+    // Break roughly half of the reports with a single bad level
+    if rng.chance(0.5) {
+        let index = rng.index(len);
+        report[index] = if rng.chance(0.5) {
+            rng.range(1..99)
+        } else {
+            report[(index + 1) % len]
+        };
+    }
+
+    report
+}
+
+/// Generates `size` reports of five to eight levels.
+pub fn generate(size: usize, seed: u64) -> String {
+    let mut rng = Rng::new(seed);
+
+    (0..size)
+        .map(|_| {
+            generate_report(&mut rng)
+                .iter()
+                .map(|level| level.to_string())
+                .collect::<Vec<_>>()
+                .join(" ")
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_generate() {
+        let input = generate(100, 1);
+
+        assert_eq!(input, generate(100, 1));
+        assert_ne!(input, generate(100, 2));
+
+        let reports = crate::parse(&input).unwrap();
+        assert_eq!(reports.len(), 100);
+        assert!(reports.iter().all(|report| report.len() >= 5));
+        assert!(crate::part1(&reports).is_ok());
+        assert!(crate::part2(&reports).is_ok());
+    }
+}
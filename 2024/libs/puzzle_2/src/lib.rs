@@ -1,28 +1,120 @@
 #![feature(vec_pop_if)]
-mod part1;
-mod part2;
+pub mod generator;
+pub mod part1;
+pub mod part2;
 
-use std::fs::File;
-
-use anyhow::Result;
+use anyhow::{Context, Result};
 
 use clap::Parser;
+use common::Variant;
+
+/// One report per line, each a list of levels.
+pub type Input = Vec<Vec<usize>>;
+
+pub fn parse(input: &str) -> Result<Input> {
+    input
+        .lines()
+        .map(|line| {
+            line.split_whitespace()
+                .map(|w| {
+                    w.parse::<usize>()
+                        .with_context(|| format!("Failed to parse {w}"))
+                })
+                .collect::<Result<Vec<_>>>()
+                .with_context(|| format!("Failed to parse report {line}"))
+        })
+        .collect()
+}
+
+pub fn part1(input: &Input) -> Result<usize> {
+    let mut count = 0;
+    for report in input {
+        if part1::is_report_save(report)? {
+            count += 1;
+        }
+    }
+
+    Ok(count)
+}
+
+pub fn part2(input: &Input) -> Result<usize> {
+    let mut count = 0;
+    for report in input {
+        if part2::is_report_save_cheat(report.clone())? {
+            count += 1;
+        }
+    }
+
+    Ok(count)
+}
+
+pub fn part2_brute_force(input: &Input) -> Result<usize> {
+    let mut count = 0;
+    for report in input {
+        if part2::is_report_save_brute_force(report)? {
+            count += 1;
+        }
+    }
+
+    Ok(count)
+}
+
+pub struct Solver;
+
+impl common::Puzzle for Solver {
+    type Input = Input;
+
+    const YEAR: u16 = 2024;
+    const DAY: u8 = 2;
+    const TITLE: &'static str = "Red-Nosed Reports";
+
+    fn parse(input: &str) -> Result<Input> {
+        parse(input)
+    }
+
+    fn part1(input: &Input) -> Result<String> {
+        part1(input).map(|answer| answer.to_string())
+    }
+
+    fn part2(input: &Input) -> Result<String> {
+        part2(input).map(|answer| answer.to_string())
+    }
+
+    fn generate(size: usize, seed: u64) -> String {
+        generator::generate(size, seed)
+    }
+
+    fn variants() -> Vec<Variant<Input>> {
+        vec![Variant {
+            part: 2,
+            name: "brute-force",
+            solve: |input| part2_brute_force(input).map(|answer| answer.to_string()),
+        }]
+    }
+}
 
 #[derive(Parser, Debug)]
-pub struct Command {}
+pub struct Command {
+    /// Run a named solution variant instead of the default one
+    #[arg(long)]
+    variant: Option<String>,
+}
 
 impl common::CommandRunner for Command {
     fn run(&self) -> Result<()> {
-        let input_file = "data/puzzle_2/input.txt";
+        common::run::<Solver>(self.variant.as_deref())?;
 
-        let file = File::open(input_file)?;
-        let result = part1::run(file)?;
-        println!("Part 1: {result}");
+        Ok(())
+    }
+}
 
-        let file = File::open(input_file)?;
-        let result = part2::run(file)?;
-        println!("Part 2: {result}");
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-        Ok(())
+    #[test]
+    fn test_fixtures() {
+        common::puzzle::check_fixtures::<Solver>(concat!(env!("CARGO_MANIFEST_DIR"), "/fixtures"))
+            .unwrap();
     }
 }
@@ -1,30 +1,6 @@
-use std::{
-    fs::File,
-    io::{self, BufRead},
-};
-
 use anyhow::Result;
 
-pub fn run(file: File) -> Result<usize> {
-    let file = io::BufReader::new(file);
-
-    let result = file.lines().fold(0, |acc, line| {
-        let line = line.unwrap();
-
-        let words = line.split_whitespace();
-
-        let report: Vec<_> = words.map(|w| w.parse::<usize>().unwrap()).collect();
-
-        if is_report_save(&report).unwrap() {
-            acc + 1
-        } else {
-            acc
-        }
-    });
-    Ok(result)
-}
-
-fn is_report_save(report: &[usize]) -> Result<bool> {
+pub fn is_report_save(report: &[usize]) -> Result<bool> {
     let len = report.len();
     let n = len - 1;
 
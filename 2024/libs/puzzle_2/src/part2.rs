@@ -1,37 +1,12 @@
-use std::{
-    fs::File,
-    io::{self, BufRead},
-};
-
 use anyhow::Result;
-use log::{debug, info};
-
-pub fn run(file: File) -> Result<usize> {
-    let file = io::BufReader::new(file);
-
-    let result = file.lines().fold(0, |acc, line| {
-        let line = line.unwrap();
-
-        let words = line.split_whitespace();
-
-        let report: Vec<_> = words.map(|w| w.parse::<usize>().unwrap()).collect();
-
-        if is_report_save_cheat(report).unwrap() {
-            acc + 1
-        } else {
-            acc
-        }
-    });
-    Ok(result)
-}
 
 #[derive(Debug, PartialEq)]
-enum ReportSafety {
+pub enum ReportSafety {
     Save,
     Unsafe(usize),
 }
 
-fn is_report_save(report: &[usize]) -> Result<ReportSafety> {
+pub fn is_report_save(report: &[usize]) -> Result<ReportSafety> {
     let len = report.len();
     let n = len - 1;
 
@@ -67,7 +42,7 @@ fn is_report_save(report: &[usize]) -> Result<ReportSafety> {
     Ok(ReportSafety::Save)
 }
 
-fn is_report_save_cheat(report: Vec<usize>) -> Result<bool> {
+pub fn is_report_save_cheat(report: Vec<usize>) -> Result<bool> {
     let is_save = is_report_save(&report)?;
 
     match is_save {
@@ -110,6 +85,24 @@ fn is_report_save_cheat(report: Vec<usize>) -> Result<bool> {
     }
 }
 
+/// Reference for [`is_report_save_cheat`], tries every single removal.
+pub fn is_report_save_brute_force(report: &[usize]) -> Result<bool> {
+    if matches!(is_report_save(report)?, ReportSafety::Save) {
+        return Ok(true);
+    }
+
+    for index in 0..report.len() {
+        let mut cheat_report = report.to_vec();
+        cheat_report.remove(index);
+
+        if matches!(is_report_save(&cheat_report)?, ReportSafety::Save) {
+            return Ok(true);
+        }
+    }
+
+    Ok(false)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -177,4 +170,24 @@ mod tests {
         assert_eq!(is_report_save_cheat(vec![1, 2, 3, 4, 3]).unwrap(), true);
         assert_eq!(is_report_save_cheat(vec![9, 8, 7, 6, 7]).unwrap(), true);
     }
+
+    #[test]
+    fn test_brute_force() {
+        let reports = vec![
+            vec![7, 6, 4, 2, 1],
+            vec![1, 2, 7, 8, 9],
+            vec![1, 3, 13, 4, 5],
+            vec![48, 46, 47, 49, 51, 54, 56],
+            vec![5, 1, 2, 3, 4, 5],
+            vec![9, 8, 7, 6, 7],
+        ];
+
+        for report in reports {
+            assert_eq!(
+                is_report_save_brute_force(&report).unwrap(),
+                is_report_save_cheat(report.clone()).unwrap(),
+                "{report:?}"
+            );
+        }
+    }
 }
@@ -0,0 +1,51 @@
+use common::rng::Rng;
+
+const JUNK: [&str; 16] = [
+    "x", "%", "&", "!", "@", "^", "[", "]", "(", ")", ",", "+", "what()", "select()", "mul", " ",
+];
+
+fn generate_instruction(rng: &mut Rng) -> String {
+    let x = rng.range(1..1000);
+    let y = rng.range(1..1000);
+
+    match rng.range(0..10) {
+        0 => "do()".to_string(),
+        1 => "don't()".to_string(),
+        2 => format!("mul({x},{y}]"),
+        3 => format!("mul ( {x} , {y} )"),
+        _ => format!("mul({x},{y})"),
+    }
+}
+
+/// Generates corrupted memory with `size` instructions, intact or broken,
+/// separated by junk.
+pub fn generate(size: usize, seed: u64) -> String {
+    let mut rng = Rng::new(seed);
+    let mut memory = String::new();
+
+    for _ in 0..size {
+        for _ in 0..rng.range(0..6) {
+            memory.push_str(rng.choose(&JUNK));
+        }
+        memory.push_str(&generate_instruction(&mut rng));
+    }
+
+    memory
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_generate() {
+        let input = generate(100, 1);
+
+        assert_eq!(input, generate(100, 1));
+        assert_ne!(input, generate(100, 2));
+
+        let input = crate::parse(&input).unwrap();
+        assert!(crate::part1(&input).unwrap() > 0);
+        assert!(crate::part2(&input).is_ok());
+    }
+}
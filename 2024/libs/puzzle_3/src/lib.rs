@@ -1,29 +1,76 @@
 #![feature(vec_pop_if)]
-mod parser;
-mod part1;
-mod part2;
-
-use std::fs::File;
+pub mod generator;
+pub mod parser;
+pub mod part1;
+pub mod part2;
 
 use anyhow::Result;
 
 use clap::Parser;
 
+/// The corrupted memory of the computer.
+pub type Input = String;
+
+pub fn parse(input: &str) -> Result<Input> {
+    Ok(input.to_string())
+}
+
+pub fn part1(input: &Input) -> Result<isize> {
+    part1::compute(input.clone())
+}
+
+pub fn part2(input: &Input) -> Result<isize> {
+    part2::compute(input.clone())
+}
+
+pub struct Solver;
+
+impl common::Puzzle for Solver {
+    type Input = Input;
+
+    const YEAR: u16 = 2024;
+    const DAY: u8 = 3;
+    const TITLE: &'static str = "Mull It Over";
+
+    fn parse(input: &str) -> Result<Input> {
+        parse(input)
+    }
+
+    fn part1(input: &Input) -> Result<String> {
+        part1(input).map(|answer| answer.to_string())
+    }
+
+    fn part2(input: &Input) -> Result<String> {
+        part2(input).map(|answer| answer.to_string())
+    }
+
+    fn generate(size: usize, seed: u64) -> String {
+        generator::generate(size, seed)
+    }
+}
+
 #[derive(Parser, Debug)]
-pub struct Command {}
+pub struct Command {
+    /// Run a named solution variant instead of the default one
+    #[arg(long)]
+    variant: Option<String>,
+}
 
 impl common::CommandRunner for Command {
     fn run(&self) -> Result<()> {
-        let input_file = "data/puzzle_3/input.txt";
+        common::run::<Solver>(self.variant.as_deref())?;
 
-        let file = File::open(input_file)?;
-        let result = part1::run(file)?;
-        println!("Part 1: {result}");
+        Ok(())
+    }
+}
 
-        let file = File::open(input_file)?;
-        let result = part2::run(file)?;
-        println!("Part 2: {result}");
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-        Ok(())
+    #[test]
+    fn test_fixtures() {
+        common::puzzle::check_fixtures::<Solver>(concat!(env!("CARGO_MANIFEST_DIR"), "/fixtures"))
+            .unwrap();
     }
 }
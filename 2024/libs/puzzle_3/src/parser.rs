@@ -1,7 +1,10 @@
 pub use nom::bytes::complete::tag;
 use nom::{
     self,
+    branch::alt,
     bytes::complete::take_until,
+    combinator::map,
+    error::{Error, ErrorKind},
     multi::many0,
     sequence::{delimited, separated_pair},
     IResult,
@@ -14,6 +17,13 @@ pub struct Mult {
     pub y: i32,
 }
 
+#[derive(Debug, PartialEq)]
+enum Instruction {
+    Mult(Mult),
+    Do,
+    Dont,
+}
+
 fn parse_input(input: &str) -> IResult<&str, &str> {
     tag("mul")(input)
 }
@@ -52,6 +62,38 @@ fn parse_all(input: &str) -> IResult<&str, Vec<Mult>> {
     many0(parse_corrupt)(input)
 }
 
+fn parse_instruction(input: &str) -> IResult<&str, Instruction> {
+    alt((
+        map(parse_mult, Instruction::Mult),
+        map(tag("do()"), |_| Instruction::Do),
+        map(tag("don't()"), |_| Instruction::Dont),
+    ))(input)
+}
+
+fn parse_corrupt_instruction(input: &str) -> IResult<&str, Instruction> {
+    let mut remaining = input;
+
+    loop {
+        if remaining.is_empty() {
+            return Err(nom::Err::Error(Error::new(remaining, ErrorKind::Eof)));
+        }
+
+        match parse_instruction(remaining) {
+            Ok(result) => return Ok(result),
+            Err(nom::Err::Error(_)) => {
+                let mut chars = remaining.chars();
+                chars.next();
+                remaining = chars.as_str();
+            }
+            Err(error) => return Err(error),
+        }
+    }
+}
+
+fn parse_all_instructions(input: &str) -> IResult<&str, Vec<Instruction>> {
+    many0(parse_corrupt_instruction)(input)
+}
+
 pub fn extract_mult(input: String) -> Result<Vec<Mult>> {
   let result = parse_all(input.as_str());
 
@@ -63,13 +105,25 @@ pub fn extract_mult(input: String) -> Result<Vec<Mult>> {
 }
 
 pub fn extract_mult_conditional(input: String) -> Result<Vec<Mult>> {
-  let result = parse_all(input.as_str());
-
-  match result {
-    Ok((_, mults)) => Ok(mults),
-    Err(_) => bail!("parsing failed"),
-  }
+    let result = parse_all_instructions(input.as_str());
+
+    let instructions = match result {
+        Ok((_, instructions)) => instructions,
+        Err(_) => bail!("parsing failed"),
+    };
+
+    let mut enabled = true;
+    let mut mults = Vec::new();
+    for instruction in instructions {
+        match instruction {
+            Instruction::Do => enabled = true,
+            Instruction::Dont => enabled = false,
+            Instruction::Mult(mult) if enabled => mults.push(mult),
+            Instruction::Mult(_) => {}
+        }
+    }
 
+    Ok(mults)
 }
 
 
@@ -153,4 +207,10 @@ mod test {
         );
         assert_eq!(remaining, ")");
     }
+    #[test]
+    fn test_extract_mult_conditional() {
+        let input = "xmul(2,4)&mul[3,7]!^don't()_mul(5,5)+mul(32,64](mul(11,8)undo()?mul(8,5))";
+        let mults = extract_mult_conditional(input.to_string()).unwrap();
+        assert_eq!(mults, vec![Mult { x: 2, y: 4 }, Mult { x: 8, y: 5 }]);
+    }
 }
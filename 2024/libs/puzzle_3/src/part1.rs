@@ -1,23 +1,8 @@
-use std::{
-    fs::File,
-    io::{self, BufRead, Read},
-};
-
 use anyhow::Result;
 
 use crate::parser::extract_mult;
 
-pub fn run(file: File) -> Result<isize> {
-    let mut file = io::BufReader::new(file);
-    
-    let mut buffer = String::new();
-
-    file.read_to_string(&mut buffer)?;
-
-    compute(buffer)
-}
-
-fn compute(input: String) -> Result<isize> {
+pub fn compute(input: String) -> Result<isize> {
   let mult = extract_mult(input)?;
 
   let result = mult.iter().fold(0, |acc, mul| {
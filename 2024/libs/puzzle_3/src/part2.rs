@@ -1,19 +1,8 @@
-use std::{
-    fs::File,
-    io::{self, BufRead},
-};
-
 use anyhow::Result;
 
 use crate::parser::extract_mult_conditional;
 
-pub fn run(file: File) -> Result<usize> {
-    let file = io::BufReader::new(file);
-
-    Ok(0)
-}
-
-fn compute(input: String) -> Result<isize> {
+pub fn compute(input: String) -> Result<isize> {
   let mult = extract_mult_conditional(input)?;
 
   let result = mult.iter().fold(0, |acc, mul| {